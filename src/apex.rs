@@ -0,0 +1,1002 @@
+//! Advanced Pedometer and Event detection (APEX) features
+//!
+//! The APEX features are implemented by the on-chip Digital Motion Processor
+//! (DMP), which operates on accelerometer data. As such, the accelerometer
+//! must be enabled for any of these features to function.
+//!
+//! The ICM-42670 supports the pedometer, tilt detection, Significant Motion
+//! Detection, and freefall detection (including its low-g and high-g
+//! building blocks). Unlike some other members of the family it does not
+//! implement raise-to-wake or lower-to-sleep gesture detection; tilt
+//! detection is the closest available alternative for wrist-worn devices.
+//!
+//! [`Icm42670::init_apex`] must be called once before enabling any of the
+//! APEX features.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+
+use crate::{
+    config::{ApexFeatures, Bitfield, InterruptPin},
+    error::SensorError,
+    mode::{MregAccessible, PowerState},
+    register::{field, Bank0, Mreg1, RegisterBank},
+    Error,
+    Icm42670,
+    Interface,
+};
+
+/// DMP output data rate selection values
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DmpOdr {
+    /// 25 Hz
+    Hz25  = 0b00,
+    /// 50 Hz
+    Hz50  = 0b10,
+    /// 100 Hz
+    Hz100 = 0b11,
+    /// 400 Hz
+    Hz400 = 0b01,
+}
+
+impl DmpOdr {
+    pub fn as_f32(self) -> f32 {
+        use DmpOdr::*;
+
+        match self {
+            Hz25 => 25.0,
+            Hz50 => 50.0,
+            Hz100 => 100.0,
+            Hz400 => 400.0,
+        }
+    }
+}
+
+impl Bitfield for DmpOdr {
+    const BITMASK: u8 = field::DMP_ODR.mask();
+
+    fn bits(self) -> u8 {
+        field::DMP_ODR.bits(self as u8)
+    }
+}
+
+impl Default for DmpOdr {
+    fn default() -> Self {
+        Self::Hz50
+    }
+}
+
+impl TryFrom<u8> for DmpOdr {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use DmpOdr::*;
+
+        match value {
+            0b00 => Ok(Hz25),
+            0b01 => Ok(Hz400),
+            0b10 => Ok(Hz50),
+            0b11 => Ok(Hz100),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Time for which the device must remain tilted before a tilt event is
+/// detected
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TiltWaitTime {
+    /// 0 seconds
+    Secs0 = 0,
+    /// 2 seconds
+    Secs2 = 1,
+    /// 4 seconds
+    Secs4 = 2,
+    /// 6 seconds
+    Secs6 = 3,
+}
+
+impl Bitfield for TiltWaitTime {
+    const BITMASK: u8 = field::TILT_WAIT_TIME_SEL.mask();
+
+    fn bits(self) -> u8 {
+        field::TILT_WAIT_TIME_SEL.bits(self as u8)
+    }
+}
+
+impl Default for TiltWaitTime {
+    fn default() -> Self {
+        Self::Secs4
+    }
+}
+
+/// Tilt detection configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TiltConfig {
+    /// Time for which the device must remain tilted before an event is
+    /// detected
+    pub wait_time: TiltWaitTime,
+    /// Interrupt pin to route tilt events to, if any
+    pub interrupt: Option<InterruptPin>,
+}
+
+/// Pedometer sensitivity modes
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PedometerMode {
+    /// Normal mode
+    Normal   = 0,
+    /// Slow walk mode, with increased sensitivity at the expense of more false
+    /// positives
+    SlowWalk = 1,
+}
+
+impl Bitfield for PedometerMode {
+    const BITMASK: u8 = field::SENSITIVITY_MODE.mask();
+
+    fn bits(self) -> u8 {
+        field::SENSITIVITY_MODE.bits(self as u8)
+    }
+}
+
+impl Default for PedometerMode {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+/// Pedometer configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PedometerConfig {
+    /// Sensitivity mode of the pedometer
+    pub mode: PedometerMode,
+    /// Interrupt pin to route step detection events to, if any
+    pub interrupt: Option<InterruptPin>,
+}
+
+/// Activity classification reported by the pedometer
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Activity {
+    /// Activity could not be classified, or the device reported the reserved
+    /// classification
+    Unknown = 0,
+    /// Walking
+    Walk    = 1,
+    /// Running
+    Run     = 2,
+}
+
+impl From<u8> for Activity {
+    fn from(value: u8) -> Self {
+        use Activity::*;
+
+        // Every classification other than walking and running, including the
+        // reserved value 3, is treated as unknown rather than as an error so
+        // that it does not fail an entire burst read of the APEX data.
+        match value {
+            1 => Walk,
+            2 => Run,
+            _ => Unknown,
+        }
+    }
+}
+
+/// Sensitivity of Significant Motion Detection
+///
+/// Higher levels detect significant motion more readily, at the expense of
+/// more false positives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SmdSensitivity {
+    /// Level 0 (least sensitive)
+    Level0 = 0,
+    /// Level 1
+    Level1 = 1,
+    /// Level 2
+    Level2 = 2,
+    /// Level 3
+    Level3 = 3,
+    /// Level 4 (most sensitive)
+    Level4 = 4,
+}
+
+impl Bitfield for SmdSensitivity {
+    const BITMASK: u8 = field::SMD_SENSITIVITY_SEL.mask();
+
+    fn bits(self) -> u8 {
+        field::SMD_SENSITIVITY_SEL.bits(self as u8)
+    }
+}
+
+impl Default for SmdSensitivity {
+    fn default() -> Self {
+        Self::Level0
+    }
+}
+
+/// Significant Motion Detection configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SmdConfig {
+    /// Sensitivity of the detection
+    pub sensitivity: SmdSensitivity,
+    /// Interrupt pin to route significant motion events to, if any
+    pub interrupt: Option<InterruptPin>,
+}
+
+/// Freefall detection configuration
+///
+/// Durations are specified as 4-bit selection values, with higher values
+/// corresponding to longer durations; refer to the `APEX_CONFIG9` and
+/// `APEX_CONFIG12` register descriptions in the datasheet for the exact
+/// durations which they represent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FreefallConfig {
+    /// Minimum duration of a freefall for it to be detected (0-15)
+    pub min_duration: u8,
+    /// Maximum duration of a freefall for it to be detected (0-15)
+    pub max_duration: u8,
+    /// Time for which further freefall events are ignored after one has been
+    /// detected (0-15)
+    pub debounce: u8,
+    /// Interrupt pin to route freefall events to, if any
+    pub interrupt: Option<InterruptPin>,
+}
+
+impl Default for FreefallConfig {
+    fn default() -> Self {
+        Self {
+            min_duration: 0,
+            max_duration: 5,
+            debounce: 7,
+            interrupt: None,
+        }
+    }
+}
+
+/// Freefall event read from the APEX data registers
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FreefallEvent {
+    /// Duration of the freefall, in samples at the DMP output data rate
+    pub duration: u16,
+}
+
+impl FreefallEvent {
+    /// Duration of the freefall in seconds, given the DMP output data rate
+    pub fn duration_secs(&self, dmp_odr: DmpOdr) -> f32 {
+        self.duration as f32 / dmp_odr.as_f32()
+    }
+
+    /// Estimated distance fallen in metres, given the DMP output data rate
+    pub fn distance(&self, dmp_odr: DmpOdr) -> f32 {
+        let t = self.duration_secs(dmp_odr);
+        0.5 * 9.80665 * t * t
+    }
+}
+
+/// Low-g detection configuration
+///
+/// Low-g detection is performed by the same engine as freefall detection, and
+/// as such runs whenever freefall detection is enabled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LowGConfig {
+    /// Peak acceleration threshold below which low-g is detected, in steps of
+    /// 31.25 mg starting from 31.25 mg (0-31)
+    pub threshold: u8,
+    /// Hysteresis applied to the threshold, in steps of 31.25 mg starting from
+    /// 31.25 mg (0-7)
+    pub hysteresis: u8,
+    /// Number of consecutive samples for which acceleration must remain below
+    /// the threshold, minus one (0-7)
+    pub debounce: u8,
+    /// Interrupt pin to route low-g events to, if any
+    pub interrupt: Option<InterruptPin>,
+}
+
+impl Default for LowGConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 10,
+            hysteresis: 3,
+            debounce: 3,
+            interrupt: None,
+        }
+    }
+}
+
+/// High-g detection configuration
+///
+/// High-g detection is performed by the same engine as freefall detection,
+/// where it is used to detect the impact which ends a freefall, and as such
+/// runs whenever freefall detection is enabled. The device does not provide a
+/// dedicated high-g interrupt; high-g events are reported as the end of a
+/// freefall.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HighGConfig {
+    /// Peak acceleration threshold above which high-g is detected, in steps of
+    /// 250 mg starting from 250 mg (0-31)
+    pub threshold: u8,
+    /// Hysteresis applied to the threshold, in steps of 31.25 mg starting from
+    /// 31.25 mg (0-7)
+    pub hysteresis: u8,
+    /// Number of consecutive samples for which acceleration must remain above
+    /// the threshold, minus one (0-7)
+    pub duration: u8,
+}
+
+impl Default for HighGConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 15,
+            hysteresis: 3,
+            duration: 3,
+        }
+    }
+}
+
+/// Interrupts raised by the APEX features
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ApexInterrupt {
+    /// A step has been detected by the pedometer
+    StepDetected,
+    /// The pedometer's step count has overflowed
+    StepCountOverflow,
+    /// A tilt has been detected
+    Tilt,
+    /// A freefall has been detected
+    Freefall,
+    /// Low-g has been detected
+    LowG,
+    /// Significant motion has been detected
+    SignificantMotion,
+}
+
+impl ApexInterrupt {
+    /// Bit mask of the interrupt's enable bit within its `INT_SOURCE*` register
+    fn mask(self) -> u8 {
+        use ApexInterrupt::*;
+
+        // `SMD_INT*_EN` is held in `INT_SOURCE1`/`INT_SOURCE4`, while the remaining
+        // enable bits are held in `INT_SOURCE6`/`INT_SOURCE7`
+        match self {
+            StepDetected => field::STEP_DET_INT1_EN.mask(),
+            StepCountOverflow => field::STEP_CNT_OFL_INT1_EN.mask(),
+            Tilt => field::TILT_DET_INT1_EN.mask(),
+            Freefall => field::FF_DET_INT1_EN.mask(),
+            LowG => field::LOWG_DET_INT1_EN.mask(),
+            SignificantMotion => field::SMD_INT1_EN.mask(),
+        }
+    }
+}
+
+/// Contents of the APEX data registers
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ApexData {
+    /// Number of steps counted by the pedometer
+    pub step_count: u16,
+    /// Walk/run cadence, as the number of samples at the DMP output data rate
+    /// between two consecutive steps, in unsigned 6.2 fixed point format
+    pub step_cadence: u8,
+    /// Activity classification reported by the pedometer
+    pub activity: Activity,
+    /// Is the DMP idle?
+    pub dmp_idle: bool,
+    /// Duration of the most recent freefall, in samples at the DMP output data
+    /// rate
+    pub freefall_duration: u16,
+}
+
+/// Host-side accumulator which extends the 16-bit hardware step count into a
+/// monotonically increasing 32-bit count
+///
+/// The hardware step count wraps around once it exceeds `u16::MAX`. Provided
+/// that the step count is read at least once every 65,535 steps, any
+/// wrap-around is detected and accounted for.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StepCounter {
+    /// Most recently observed hardware step count
+    last: u16,
+    /// Accumulated step count
+    total: u32,
+}
+
+impl StepCounter {
+    /// Create a new step counter, starting from zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the accumulated step count using a step count read from the
+    /// device, returning the new total
+    pub fn update(&mut self, count: u16) -> u32 {
+        let steps = count.wrapping_sub(self.last);
+        self.last = count;
+        self.total = self.total.wrapping_add(steps as u32);
+
+        self.total
+    }
+
+    /// Return the accumulated step count
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// Reset the step counter
+    ///
+    /// This must be called whenever the hardware step count is reset, for
+    /// example when the DMP is re-initialized, as otherwise the reset is
+    /// interpreted as a wrap-around.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Initialize the APEX features
+    ///
+    /// This enables the APEX block and clears the DMP's memory, resetting any
+    /// state held by the APEX features (such as the step count). It must be
+    /// called before enabling any of the APEX features.
+    pub fn init_apex<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.write_mfield(delay, field::APEX_DISABLE, 0)?;
+
+        self.write_field(field::DMP_MEM_RESET_EN, 1)?;
+        delay.delay_ms(1);
+
+        Ok(())
+    }
+
+    /// Return the currently configured output data rate for the DMP
+    pub fn dmp_odr(&mut self) -> Result<DmpOdr, Error<E>> {
+        let bits = self.read_field(field::DMP_ODR)?;
+        let odr = DmpOdr::try_from(bits)?;
+
+        Ok(odr)
+    }
+
+    /// Set the output data rate of the DMP
+    ///
+    /// The APEX features process accelerometer samples at this rate, so the
+    /// accelerometer's output data rate must be at least as fast; if it is not
+    /// then an error is returned and the DMP output data rate is left
+    /// unchanged. The pedometer, tilt detection and SMD are designed to
+    /// operate at 50 Hz.
+    pub fn set_dmp_odr(&mut self, odr: DmpOdr) -> Result<(), Error<E>> {
+        if self.accel_odr()?.as_f32() < odr.as_f32() {
+            return Err(Error::SensorError(SensorError::DmpOdrMismatch));
+        }
+
+        self.update_reg(&Bank0::APEX_CONFIG1, odr.bits(), DmpOdr::BITMASK)
+    }
+
+    /// Is the DMP's power save mode enabled?
+    pub fn dmp_power_save(&mut self) -> Result<bool, Error<E>> {
+        let enabled = self.read_field(field::DMP_POWER_SAVE_EN)? != 0;
+
+        Ok(enabled)
+    }
+
+    /// Enable the DMP's power save mode
+    ///
+    /// In power save mode the DMP only processes data once Wake on Motion has
+    /// detected motion, and stops processing again once no motion has been
+    /// detected for the configured power save time. Wake on Motion must be
+    /// enabled for the APEX features to function in this mode; see
+    /// [`Icm42670::enable_wake_on_motion`].
+    pub fn enable_dmp_power_save(&mut self) -> Result<(), Error<E>> {
+        self.write_field(field::DMP_POWER_SAVE_EN, 1)
+    }
+
+    /// Disable the DMP's power save mode
+    pub fn disable_dmp_power_save(&mut self) -> Result<(), Error<E>> {
+        self.write_field(field::DMP_POWER_SAVE_EN, 0)
+    }
+
+    /// Set the time after which the DMP enters power save mode once no motion
+    /// has been detected, in steps of 4 seconds (0-15)
+    pub fn set_dmp_power_save_time<D>(&mut self, delay: &mut D, time: u8) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        if time > field::DMP_POWER_SAVE_TIME_SEL.max() {
+            return Err(Error::SensorError(SensorError::DurationOutOfRange));
+        }
+
+        self.write_mfield(delay, field::DMP_POWER_SAVE_TIME_SEL, time)
+    }
+
+    /// Enable tilt detection
+    ///
+    /// A tilt event is detected when the device is tilted by more than 35
+    /// degrees from its orientation at the time detection was enabled, and
+    /// remains tilted for the configured wait time.
+    pub fn enable_tilt_detection<D>(
+        &mut self,
+        delay: &mut D,
+        config: TiltConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let features = ApexFeatures {
+            tilt: true,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::APEX_CONFIG5,
+            config.wait_time.bits(),
+            TiltWaitTime::BITMASK,
+        )?;
+        delay.delay_ms(1);
+
+        self.init_dmp(delay)?;
+
+        self.route_apex_interrupt(delay, ApexInterrupt::Tilt, config.interrupt)?;
+
+        self.write_field(field::TILT_ENABLE, 1)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Disable tilt detection
+    pub fn disable_tilt_detection(&mut self) -> Result<(), Error<E>> {
+        let features = ApexFeatures {
+            tilt: false,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.write_field(field::TILT_ENABLE, 0)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Check whether a tilt event has been detected since it was last
+    /// reported
+    pub fn tilt_detected(&mut self) -> Result<bool, Error<E>> {
+        let detected = self.take_interrupt(field::TILT_DET_INT)?;
+
+        Ok(detected)
+    }
+
+    /// Enable the pedometer
+    ///
+    /// The pedometer requires the accelerometer to be running at 50 Hz.
+    pub fn enable_pedometer<D>(
+        &mut self,
+        delay: &mut D,
+        config: PedometerConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let features = ApexFeatures {
+            pedometer: true,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::APEX_CONFIG9,
+            config.mode.bits(),
+            PedometerMode::BITMASK,
+        )?;
+        delay.delay_ms(1);
+
+        self.init_dmp(delay)?;
+
+        self.route_apex_interrupt(delay, ApexInterrupt::StepDetected, config.interrupt)?;
+
+        self.write_field(field::PED_ENABLE, 1)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Disable the pedometer
+    pub fn disable_pedometer(&mut self) -> Result<(), Error<E>> {
+        let features = ApexFeatures {
+            pedometer: false,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.write_field(field::PED_ENABLE, 0)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Read the number of steps counted by the pedometer
+    ///
+    /// The step count wraps around once it exceeds `u16::MAX`.
+    pub fn step_count(&mut self) -> Result<u16, Error<E>> {
+        // `APEX_DATA0` holds the low byte and `APEX_DATA1` the high byte of the count
+        let mut buffer = [0u8; 2];
+        self.read_regs(&Bank0::APEX_DATA0, &mut buffer)?;
+
+        Ok(u16::from_le_bytes(buffer))
+    }
+
+    /// Route the given APEX interrupt to an interrupt pin
+    ///
+    /// An interrupt may be routed to both pins simultaneously.
+    pub fn enable_apex_interrupt<D>(
+        &mut self,
+        delay: &mut D,
+        interrupt: ApexInterrupt,
+        pin: InterruptPin,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.set_apex_interrupt(delay, interrupt, pin, true)
+    }
+
+    /// Stop routing the given APEX interrupt to an interrupt pin
+    pub fn disable_apex_interrupt<D>(
+        &mut self,
+        delay: &mut D,
+        interrupt: ApexInterrupt,
+        pin: InterruptPin,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.set_apex_interrupt(delay, interrupt, pin, false)
+    }
+
+    /// Read all of the APEX data registers
+    ///
+    /// `APEX_DATA0` through `APEX_DATA3` and `APEX_DATA4` through `APEX_DATA5`
+    /// are not contiguous, so this requires two burst reads.
+    pub fn apex_data(&mut self) -> Result<ApexData, Error<E>> {
+        let mut data = [0u8; 4];
+        self.read_regs(&Bank0::APEX_DATA0, &mut data)?;
+
+        let mut ff_dur = [0u8; 2];
+        self.read_regs(&Bank0::APEX_DATA4, &mut ff_dur)?;
+
+        Ok(ApexData {
+            step_count: u16::from_le_bytes([data[0], data[1]]),
+            step_cadence: data[2],
+            activity: Activity::from(field::ACTIVITY_CLASS.get(data[3])),
+            dmp_idle: field::DMP_IDLE.get(data[3]) != 0,
+            freefall_duration: u16::from_be_bytes(ff_dur),
+        })
+    }
+
+    /// Read the step count from the device and accumulate it using the given
+    /// step counter, returning the new total
+    pub fn update_step_counter(&mut self, counter: &mut StepCounter) -> Result<u32, Error<E>> {
+        let count = self.step_count()?;
+
+        Ok(counter.update(count))
+    }
+
+    /// Check whether the step count has overflowed since it was last
+    /// reported
+    pub fn step_count_overflowed(&mut self) -> Result<bool, Error<E>> {
+        let overflowed = self.take_interrupt(field::STEP_CNT_OVF_INT)?;
+
+        Ok(overflowed)
+    }
+
+    /// Read the walk/run cadence reported by the pedometer
+    ///
+    /// The cadence is the number of samples, at the DMP output data rate,
+    /// between two consecutive steps, and is returned in unsigned 6.2 fixed
+    /// point format.
+    pub fn step_cadence(&mut self) -> Result<u8, Error<E>> {
+        self.read_reg(&Bank0::APEX_DATA2)
+    }
+
+    /// Read the activity classification reported by the pedometer
+    pub fn activity(&mut self) -> Result<Activity, Error<E>> {
+        let bits = self.read_field(field::ACTIVITY_CLASS)?;
+
+        Ok(Activity::from(bits))
+    }
+
+    /// Check whether a step has been detected since it was last
+    /// reported
+    pub fn step_detected(&mut self) -> Result<bool, Error<E>> {
+        let detected = self.take_interrupt(field::STEP_DET_INT)?;
+
+        Ok(detected)
+    }
+
+    /// Enable Significant Motion Detection (SMD)
+    ///
+    /// Significant motion is sustained movement which is likely to result in
+    /// a change of location, such as walking, as opposed to the brief
+    /// movements detected by Wake on Motion. SMD builds upon Wake on Motion,
+    /// which must be enabled for significant motion to be detected; see
+    /// [`Icm42670::enable_wake_on_motion`].
+    pub fn enable_significant_motion_detection<D>(
+        &mut self,
+        delay: &mut D,
+        config: SmdConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let features = ApexFeatures {
+            smd: true,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::APEX_CONFIG9,
+            config.sensitivity.bits(),
+            SmdSensitivity::BITMASK,
+        )?;
+        delay.delay_ms(1);
+
+        self.init_dmp(delay)?;
+
+        self.route_apex_interrupt(delay, ApexInterrupt::SignificantMotion, config.interrupt)?;
+
+        self.write_field(field::SMD_ENABLE, 1)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Disable Significant Motion Detection (SMD)
+    pub fn disable_significant_motion_detection(&mut self) -> Result<(), Error<E>> {
+        let features = ApexFeatures {
+            smd: false,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.write_field(field::SMD_ENABLE, 0)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Check whether significant motion has been detected since it
+    /// was last reported
+    pub fn significant_motion_detected(&mut self) -> Result<bool, Error<E>> {
+        let detected = self.take_interrupt(field::SMD_INT)?;
+
+        Ok(detected)
+    }
+
+    /// Enable freefall detection
+    pub fn enable_freefall_detection<D>(
+        &mut self,
+        delay: &mut D,
+        config: FreefallConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let features = ApexFeatures {
+            freefall: true,
+            ..self.apex
+        };
+        features.validate()?;
+
+        if config.min_duration > field::FF_MIN_DURATION_SEL.max()
+            || config.max_duration > field::FF_MAX_DURATION_SEL.max()
+            || config.debounce > field::FF_DEBOUNCE_DURATION_SEL.max()
+        {
+            return Err(Error::SensorError(SensorError::DurationOutOfRange));
+        }
+
+        // The durations occupy the whole of `APEX_CONFIG12`
+        let durations = field::FF_MAX_DURATION_SEL.bits(config.max_duration)
+            | field::FF_MIN_DURATION_SEL.bits(config.min_duration);
+        self.write_mreg(delay, RegisterBank::MReg1, &Mreg1::APEX_CONFIG12, durations)?;
+
+        self.write_mfield(delay, field::FF_DEBOUNCE_DURATION_SEL, config.debounce)?;
+        delay.delay_ms(1);
+
+        self.init_dmp(delay)?;
+
+        self.route_apex_interrupt(delay, ApexInterrupt::Freefall, config.interrupt)?;
+
+        self.write_field(field::FF_ENABLE, 1)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Disable freefall detection
+    pub fn disable_freefall_detection(&mut self) -> Result<(), Error<E>> {
+        let features = ApexFeatures {
+            freefall: false,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.write_field(field::FF_ENABLE, 0)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Check whether a freefall has been detected since it was last
+    /// reported, returning the event if so
+    pub fn freefall_detected(&mut self) -> Result<Option<FreefallEvent>, Error<E>> {
+        if !self.take_interrupt(field::FF_DET_INT)? {
+            return Ok(None);
+        }
+
+        // `APEX_DATA4` holds the high byte and `APEX_DATA5` the low byte of the
+        // duration
+        let mut buffer = [0u8; 2];
+        self.read_regs(&Bank0::APEX_DATA4, &mut buffer)?;
+        let duration = u16::from_be_bytes(buffer);
+
+        Ok(Some(FreefallEvent { duration }))
+    }
+
+    /// Configure low-g detection
+    ///
+    /// Low-g detection runs as part of freefall detection; see
+    /// [`Icm42670::enable_freefall_detection`].
+    pub fn configure_low_g_detection<D>(
+        &mut self,
+        delay: &mut D,
+        config: LowGConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        if config.threshold > field::LOWG_PEAK_TH_SEL.max()
+            || config.hysteresis > field::LOWG_PEAK_TH_HYST_SEL.max()
+        {
+            return Err(Error::SensorError(SensorError::ThresholdOutOfRange));
+        } else if config.debounce > field::LOWG_TIME_TH_SEL.max() {
+            return Err(Error::SensorError(SensorError::DurationOutOfRange));
+        }
+
+        // The thresholds occupy the whole of `APEX_CONFIG10`
+        let bits = field::LOWG_PEAK_TH_SEL.bits(config.threshold)
+            | field::LOWG_TIME_TH_SEL.bits(config.debounce);
+        self.write_mreg(delay, RegisterBank::MReg1, &Mreg1::APEX_CONFIG10, bits)?;
+
+        self.write_mfield(delay, field::LOWG_PEAK_TH_HYST_SEL, config.hysteresis)?;
+
+        self.route_apex_interrupt(delay, ApexInterrupt::LowG, config.interrupt)
+    }
+
+    /// Configure high-g detection
+    ///
+    /// High-g detection runs as part of freefall detection; see
+    /// [`Icm42670::enable_freefall_detection`].
+    pub fn configure_high_g_detection<D>(
+        &mut self,
+        delay: &mut D,
+        config: HighGConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        if config.threshold > field::HIGHG_PEAK_TH_SEL.max()
+            || config.hysteresis > field::HIGHG_PEAK_TH_HYST_SEL.max()
+        {
+            return Err(Error::SensorError(SensorError::ThresholdOutOfRange));
+        } else if config.duration > field::HIGHG_TIME_TH_SEL.max() {
+            return Err(Error::SensorError(SensorError::DurationOutOfRange));
+        }
+
+        // The thresholds occupy the whole of `APEX_CONFIG11`
+        let bits = field::HIGHG_PEAK_TH_SEL.bits(config.threshold)
+            | field::HIGHG_TIME_TH_SEL.bits(config.duration);
+        self.write_mreg(delay, RegisterBank::MReg1, &Mreg1::APEX_CONFIG11, bits)?;
+
+        self.write_mfield(delay, field::HIGHG_PEAK_TH_HYST_SEL, config.hysteresis)
+    }
+
+    /// Check whether low-g has been detected since it was last
+    /// reported
+    pub fn low_g_detected(&mut self) -> Result<bool, Error<E>> {
+        let detected = self.take_interrupt(field::LOWG_DET_INT)?;
+
+        Ok(detected)
+    }
+
+    // -----------------------------------------------------------------------
+    // PRIVATE
+
+    /// Initialize the DMP, which must be done after configuring the APEX
+    /// features and before enabling them. This requires the DMP's memory to
+    /// have previously been cleared by [`Self::init_apex`].
+    fn init_dmp<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayMs<u8>,
+    {
+        self.write_field(field::DMP_INIT_EN, 1)?;
+        delay.delay_ms(50);
+
+        Ok(())
+    }
+
+    fn route_apex_interrupt(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        interrupt: ApexInterrupt,
+        pin: Option<InterruptPin>,
+    ) -> Result<(), Error<E>> {
+        for p in [InterruptPin::Int1, InterruptPin::Int2] {
+            self.set_apex_interrupt(delay, interrupt, p, pin == Some(p))?;
+        }
+
+        Ok(())
+    }
+
+    fn set_apex_interrupt(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        interrupt: ApexInterrupt,
+        pin: InterruptPin,
+        enabled: bool,
+    ) -> Result<(), Error<E>> {
+        let mask = interrupt.mask();
+        let value = if enabled { mask } else { 0 };
+
+        if let ApexInterrupt::SignificantMotion = interrupt {
+            let reg = match pin {
+                InterruptPin::Int1 => Bank0::INT_SOURCE1,
+                InterruptPin::Int2 => Bank0::INT_SOURCE4,
+            };
+
+            self.update_reg(&reg, value, mask)
+        } else {
+            let reg = match pin {
+                InterruptPin::Int1 => Mreg1::INT_SOURCE6,
+                InterruptPin::Int2 => Mreg1::INT_SOURCE7,
+            };
+
+            self.update_mreg(delay, RegisterBank::MReg1, &reg, value, mask)
+        }
+    }
+}
@@ -0,0 +1,555 @@
+//! Calibration of sensor biases and scale factors
+//!
+//! Every gyroscope reports a small, non-zero angular rate while stationary.
+//! This bias may be measured at runtime and removed either in hardware, using
+//! the user offset registers, or in software by the driver.
+//!
+//! The accelerometer is calibrated by measuring gravity with each axis in turn
+//! pointing up and then down, from which both the offset and the scale factor
+//! of each axis can be determined.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+use micromath::vector::{F32x3, I16x3};
+
+use crate::{
+    error::SensorError,
+    mode::{AccelEnabled, GyroEnabled, MregAccessible, PowerState},
+    register::Bank0,
+    Error,
+    Icm42670,
+    Interface,
+};
+
+/// Resolution of the gyroscope offsets, in LSB per dps
+const GYRO_OFFSET_LSB_PER_DPS: f32 = 32.0;
+
+/// Where a measured gyroscope bias is applied
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GyroCalibrationTarget {
+    /// Add the negated bias to the user offset registers, removing it in
+    /// hardware from every sample, including those written to the FIFO
+    OffsetRegisters,
+    /// Store the bias in the driver's [`Calibration`], subtracting it from the
+    /// data returned by [`Icm42670::gyro_norm`]
+    Software,
+}
+
+impl Default for GyroCalibrationTarget {
+    fn default() -> Self {
+        Self::OffsetRegisters
+    }
+}
+
+/// Static orientations of the device used for accelerometer calibration
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Orientation {
+    /// The X axis points up
+    XUp   = 0,
+    /// The X axis points down
+    XDown = 1,
+    /// The Y axis points up
+    YUp   = 2,
+    /// The Y axis points down
+    YDown = 3,
+    /// The Z axis points up
+    ZUp   = 4,
+    /// The Z axis points down
+    ZDown = 5,
+}
+
+/// Corrections for each axis of a single sensor
+///
+/// Corrections are applied to normalized measurements as
+/// `misalignment * ((measured - bias) * scale)`, where the scale factors are
+/// applied to each axis individually.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SensorCalibration {
+    /// Bias of each axis, in the units of the normalized measurement
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub bias: F32x3,
+    /// Scale factor of each axis
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub scale: F32x3,
+    /// Row-major matrix correcting for any misalignment between the axes, if
+    /// known
+    pub misalignment: Option<[[f32; 3]; 3]>,
+}
+
+impl Default for SensorCalibration {
+    fn default() -> Self {
+        Self {
+            bias: F32x3::new(0.0, 0.0, 0.0),
+            scale: F32x3::new(1.0, 1.0, 1.0),
+            misalignment: None,
+        }
+    }
+}
+
+impl SensorCalibration {
+    /// Apply the corrections to a normalized measurement
+    pub fn apply(&self, measured: F32x3) -> F32x3 {
+        let v = [
+            (measured.x - self.bias.x) * self.scale.x,
+            (measured.y - self.bias.y) * self.scale.y,
+            (measured.z - self.bias.z) * self.scale.z,
+        ];
+
+        match self.misalignment {
+            Some(m) => {
+                let row = |r: [f32; 3]| r[0] * v[0] + r[1] * v[1] + r[2] * v[2];
+                F32x3::new(row(m[0]), row(m[1]), row(m[2]))
+            }
+            None => F32x3::new(v[0], v[1], v[2]),
+        }
+    }
+}
+
+/// Calibration applied in software to normalized sensor data
+///
+/// Once loaded with [`Icm42670::set_calibration`], the corrections are applied
+/// transparently by [`Icm42670::accel_norm`], [`Icm42670::gyro_norm`] and
+/// [`Icm42670::read`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Calibration {
+    /// Accelerometer corrections, with the bias in g
+    pub accel: SensorCalibration,
+    /// Gyroscope corrections, with the bias in degrees per second
+    pub gyro: SensorCalibration,
+}
+
+/// Version of the serialized calibration format
+const CALIBRATION_VERSION: u8 = 1;
+/// Length of a single serialized [`SensorCalibration`]
+const SENSOR_CALIBRATION_LEN: usize = 61;
+
+impl SensorCalibration {
+    /// Serialize the corrections as little endian values, followed by a flag
+    /// indicating whether the misalignment matrix is present
+    fn write_bytes(&self, bytes: &mut [u8]) {
+        let matrix = self.misalignment.unwrap_or([[0.0; 3]; 3]);
+        let values = [self.bias.x, self.bias.y, self.bias.z]
+            .into_iter()
+            .chain([self.scale.x, self.scale.y, self.scale.z])
+            .chain(matrix.into_iter().flatten());
+
+        for (chunk, value) in bytes.chunks_exact_mut(4).zip(values) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        bytes[SENSOR_CALIBRATION_LEN - 1] = self.misalignment.is_some() as u8;
+    }
+
+    fn read_bytes(bytes: &[u8]) -> Self {
+        let value = |idx: usize| {
+            let idx = idx * 4;
+            f32::from_le_bytes([bytes[idx], bytes[idx + 1], bytes[idx + 2], bytes[idx + 3]])
+        };
+
+        let misalignment = if bytes[SENSOR_CALIBRATION_LEN - 1] != 0 {
+            let row = |r: usize| [value(6 + r * 3), value(7 + r * 3), value(8 + r * 3)];
+            Some([row(0), row(1), row(2)])
+        } else {
+            None
+        };
+
+        Self {
+            bias: F32x3::new(value(0), value(1), value(2)),
+            scale: F32x3::new(value(3), value(4), value(5)),
+            misalignment,
+        }
+    }
+}
+
+impl Calibration {
+    /// Length of a serialized calibration, in bytes
+    pub const SERIALIZED_LEN: usize = 2 + 2 * SENSOR_CALIBRATION_LEN;
+
+    /// Serialize the calibration to a byte blob, suitable for persisting to
+    /// non-volatile memory
+    ///
+    /// The blob begins with a format version and ends with a checksum, so that
+    /// blank or corrupted memory is detected by [`Calibration::from_bytes`].
+    pub fn to_bytes(&self) -> [u8; Self::SERIALIZED_LEN] {
+        let mut bytes = [0u8; Self::SERIALIZED_LEN];
+        let (version, rest) = bytes.split_at_mut(1);
+        let (accel, rest) = rest.split_at_mut(SENSOR_CALIBRATION_LEN);
+        let (gyro, _) = rest.split_at_mut(SENSOR_CALIBRATION_LEN);
+
+        version[0] = CALIBRATION_VERSION;
+        self.accel.write_bytes(accel);
+        self.gyro.write_bytes(gyro);
+        bytes[Self::SERIALIZED_LEN - 1] = checksum(&bytes[..Self::SERIALIZED_LEN - 1]);
+
+        bytes
+    }
+
+    /// Deserialize a calibration from a byte blob produced by
+    /// [`Calibration::to_bytes`]
+    ///
+    /// Returns [`SensorError::CorruptCalibration`] if the blob has the wrong
+    /// length or version, or if its checksum does not match.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SensorError> {
+        if bytes.len() != Self::SERIALIZED_LEN
+            || bytes[0] != CALIBRATION_VERSION
+            || bytes[Self::SERIALIZED_LEN - 1] != checksum(&bytes[..Self::SERIALIZED_LEN - 1])
+        {
+            return Err(SensorError::CorruptCalibration);
+        }
+
+        let accel = &bytes[1..1 + SENSOR_CALIBRATION_LEN];
+        let gyro = &bytes[1 + SENSOR_CALIBRATION_LEN..1 + 2 * SENSOR_CALIBRATION_LEN];
+
+        Ok(Self {
+            accel: SensorCalibration::read_bytes(accel),
+            gyro: SensorCalibration::read_bytes(gyro),
+        })
+    }
+}
+
+/// CRC-8 checksum, using the polynomial `x⁸ + x² + x + 1`, which detects
+/// every single-byte error and, unlike a plain sum, reordered bytes
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |crc, &byte| {
+        (0..8).fold(crc ^ byte, |crc, _| {
+            if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Guided six-position accelerometer calibration
+///
+/// Place the device so that each axis in turn points straight up and then
+/// straight down, holding it still while a measurement is taken in each
+/// [`Orientation`], for example using [`Icm42670::accel_mean`]. Once all six
+/// measurements have been recorded the corrections can be computed.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SixPositionCalibration {
+    /// Measurement taken in each orientation, in g
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    measurements: [Option<F32x3>; 6],
+}
+
+impl SixPositionCalibration {
+    /// Create a new calibration with no measurements recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the measurement taken in the given orientation, in g
+    ///
+    /// Any measurement previously recorded in the same orientation is
+    /// replaced.
+    pub fn record(&mut self, orientation: Orientation, measurement: F32x3) {
+        self.measurements[orientation as usize] = Some(measurement);
+    }
+
+    /// Have measurements been recorded in all six orientations?
+    pub fn is_complete(&self) -> bool {
+        self.measurements.iter().all(Option::is_some)
+    }
+
+    /// Compute the bias and scale corrections from the recorded measurements
+    ///
+    /// Returns [`SensorError::MissingCalibrationMeasurement`] if any
+    /// measurement is missing, or [`SensorError::InvalidCalibration`] if any
+    /// axis did not measure more gravity pointing up than pointing down.
+    pub fn compute(&self) -> Result<SensorCalibration, SensorError> {
+        use Orientation::*;
+
+        let measurement = |orientation: Orientation| {
+            self.measurements[orientation as usize]
+                .ok_or(SensorError::MissingCalibrationMeasurement)
+        };
+
+        // Each axis should measure +1g pointing up and -1g pointing down, so its
+        // bias lies midway between the two and its scale maps their difference
+        // onto 2g.
+        let axis = |up: f32, down: f32| {
+            if up > down {
+                Ok(((up + down) / 2.0, 2.0 / (up - down)))
+            } else {
+                Err(SensorError::InvalidCalibration)
+            }
+        };
+
+        let (x_bias, x_scale) = axis(measurement(XUp)?.x, measurement(XDown)?.x)?;
+        let (y_bias, y_scale) = axis(measurement(YUp)?.y, measurement(YDown)?.y)?;
+        let (z_bias, z_scale) = axis(measurement(ZUp)?.z, measurement(ZDown)?.z)?;
+
+        Ok(SensorCalibration {
+            bias: F32x3::new(x_bias, y_bias, z_bias),
+            scale: F32x3::new(x_scale, y_scale, z_scale),
+            misalignment: None,
+        })
+    }
+}
+
+/// Round to the nearest integer, as `f32::round` is unavailable in `no_std`
+fn round(value: f32) -> i32 {
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Measure and remove the bias of the gyroscope, returning the bias in
+    /// degrees per second
+    ///
+    /// The device must remain stationary while `samples` samples are collected
+    /// at the configured output data rate, and the gyroscope must already be
+    /// enabled and running. Each sample is awaited by polling the data ready
+    /// status, so any data ready interrupt pending when this is called is
+    /// consumed. If no sample arrives within two output data rate periods,
+    /// [`SensorError::DataReadyTimeout`] is returned.
+    ///
+    /// When targeting the offset registers, returns
+    /// [`SensorError::OffsetOutOfRange`] if the bias is too large to be removed
+    /// by them.
+    pub fn calibrate_gyro<D>(
+        &mut self,
+        delay: &mut D,
+        samples: u16,
+        target: GyroCalibrationTarget,
+    ) -> Result<F32x3, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: GyroEnabled + MregAccessible,
+    {
+        let scale = self.gyro_range()?.scale_factor();
+        let bias = self.mean(delay, samples, scale, Self::gyro_raw)?;
+
+        match target {
+            GyroCalibrationTarget::OffsetRegisters => {
+                // Any existing offsets are already reflected in the measured bias, so
+                // the new offsets are relative to them.
+                let current = self.gyro_offsets(delay)?;
+                let offset = |current: i16, bias: f32| {
+                    current as i32 - round(bias * GYRO_OFFSET_LSB_PER_DPS)
+                };
+                let offsets = [
+                    offset(current.x, bias.x),
+                    offset(current.y, bias.y),
+                    offset(current.z, bias.z),
+                ];
+
+                // Offsets beyond the range of an `i16` certainly do not fit within
+                // 12 bits either, and are otherwise rejected by `set_gyro_offsets`.
+                let [x, y, z] =
+                    offsets.map(|o| i16::try_from(o).map_err(|_| SensorError::OffsetOutOfRange));
+                self.set_gyro_offsets(delay, I16x3::new(x?, y?, z?))?;
+            }
+            GyroCalibrationTarget::Software => self.calibration.gyro.bias = bias,
+        }
+
+        Ok(bias)
+    }
+
+    /// Measure the mean acceleration over `samples` samples, in g
+    ///
+    /// No corrections are applied to the measurement. Samples are awaited in
+    /// the same way as by [`Icm42670::calibrate_gyro`].
+    pub fn accel_mean<D>(&mut self, delay: &mut D, samples: u16) -> Result<F32x3, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: AccelEnabled,
+    {
+        let scale = self.accel_range()?.scale_factor();
+
+        self.mean(delay, samples, scale, |imu| {
+            imu.read_reg_vector(&Bank0::ACCEL_DATA_X1)
+        })
+    }
+
+    /// Return the calibration applied in software to normalized sensor data
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Load a calibration to be applied in software to normalized sensor data
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// Average `samples` samples, each awaited by polling the data ready
+    /// status, and scale the result using the provided sensitivity.
+    pub(crate) fn mean(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        samples: u16,
+        scale: f32,
+        read: fn(&mut Self) -> Result<I16x3, Error<E>>,
+    ) -> Result<F32x3, Error<E>> {
+        if samples == 0 {
+            return Err(Error::SensorError(SensorError::InvalidSampleCount));
+        }
+
+        let mut sum = [0i32; 3];
+        for _ in 0..samples {
+            self.wait_for_data(delay)?;
+
+            let raw = read(self)?;
+            sum[0] += raw.x as i32;
+            sum[1] += raw.y as i32;
+            sum[2] += raw.z as i32;
+        }
+
+        let scale = scale * samples as f32;
+
+        Ok(F32x3::new(
+            sum[0] as f32 / scale,
+            sum[1] as f32 / scale,
+            sum[2] as f32 / scale,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calibration() -> Calibration {
+        Calibration {
+            accel: SensorCalibration {
+                bias: F32x3::new(0.01, -0.02, 0.03),
+                scale: F32x3::new(1.01, 0.99, 1.02),
+                misalignment: Some([[1.0, 0.01, 0.0], [-0.01, 1.0, 0.02], [0.0, -0.02, 1.0]]),
+            },
+            gyro: SensorCalibration {
+                bias: F32x3::new(-0.5, 0.25, 1.5),
+                ..SensorCalibration::default()
+            },
+        }
+    }
+
+    #[test]
+    fn serialization_round_trips() {
+        let calibration = calibration();
+        let bytes = calibration.to_bytes();
+
+        assert_eq!(bytes[0], CALIBRATION_VERSION);
+        assert_eq!(Calibration::from_bytes(&bytes).unwrap(), calibration);
+    }
+
+    #[test]
+    fn corruption_is_detected() {
+        let bytes = calibration().to_bytes();
+
+        for idx in 0..Calibration::SERIALIZED_LEN {
+            let mut corrupt = bytes;
+            corrupt[idx] ^= 0x10;
+
+            assert!(matches!(
+                Calibration::from_bytes(&corrupt),
+                Err(SensorError::CorruptCalibration)
+            ));
+        }
+    }
+
+    #[test]
+    fn reordering_is_detected() {
+        let mut bytes = calibration().to_bytes();
+        bytes.swap(5, 6);
+
+        assert!(matches!(
+            Calibration::from_bytes(&bytes),
+            Err(SensorError::CorruptCalibration)
+        ));
+    }
+
+    #[test]
+    fn wrong_length_or_blank_memory_is_rejected() {
+        let bytes = calibration().to_bytes();
+
+        assert!(matches!(
+            Calibration::from_bytes(&bytes[..Calibration::SERIALIZED_LEN - 1]),
+            Err(SensorError::CorruptCalibration)
+        ));
+        assert!(matches!(
+            Calibration::from_bytes(&[0xFF; Calibration::SERIALIZED_LEN]),
+            Err(SensorError::CorruptCalibration)
+        ));
+    }
+
+    /// Record measurements of an accelerometer with the given bias and
+    /// sensitivity in every orientation
+    fn six_position(bias: [f32; 3], gain: [f32; 3]) -> SixPositionCalibration {
+        use Orientation::*;
+
+        let mut calibration = SixPositionCalibration::new();
+        let axis = |axis: usize, g: f32| bias[axis] + gain[axis] * g;
+        let measurement = |along: usize, g: f32| {
+            let g = |a: usize| if a == along { g } else { 0.0 };
+            F32x3::new(axis(0, g(0)), axis(1, g(1)), axis(2, g(2)))
+        };
+
+        calibration.record(XUp, measurement(0, 1.0));
+        calibration.record(XDown, measurement(0, -1.0));
+        calibration.record(YUp, measurement(1, 1.0));
+        calibration.record(YDown, measurement(1, -1.0));
+        calibration.record(ZUp, measurement(2, 1.0));
+        calibration.record(ZDown, measurement(2, -1.0));
+
+        calibration
+    }
+
+    fn assert_close(actual: F32x3, expected: [f32; 3]) {
+        for (actual, expected) in [actual.x, actual.y, actual.z].iter().zip(expected) {
+            let error = actual - expected;
+            assert!(-1e-6 < error && error < 1e-6, "{} != {}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn six_position_compute() {
+        let calibration = six_position([0.05, -0.02, 0.1], [1.02, 0.98, 1.0]);
+        assert!(calibration.is_complete());
+
+        let corrections = calibration.compute().unwrap();
+        assert_close(corrections.bias, [0.05, -0.02, 0.1]);
+        assert_close(corrections.scale, [1.0 / 1.02, 1.0 / 0.98, 1.0]);
+        assert_eq!(corrections.misalignment, None);
+
+        // Corrected measurements read exactly 1g along each axis in turn.
+        let corrected = corrections.apply(F32x3::new(0.05 + 1.02, -0.02, 0.1));
+        assert_close(corrected, [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn six_position_missing_measurement() {
+        let mut calibration = SixPositionCalibration::new();
+        calibration.record(Orientation::XUp, F32x3::new(1.0, 0.0, 0.0));
+
+        assert!(!calibration.is_complete());
+        assert!(matches!(
+            calibration.compute(),
+            Err(SensorError::MissingCalibrationMeasurement)
+        ));
+    }
+
+    #[test]
+    fn six_position_inverted_axis() {
+        let calibration = six_position([0.0; 3], [1.0, -1.0, 1.0]);
+
+        assert!(matches!(
+            calibration.compute(),
+            Err(SensorError::InvalidCalibration)
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::error::SensorError;
+use crate::{error::SensorError, register::field};
 
 pub(crate) trait Bitfield {
     const BITMASK: u8;
@@ -10,6 +10,8 @@ pub(crate) trait Bitfield {
 
 /// I²C slave addresses, determined by the logic level of pin `AP_AD0`
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Address {
     /// `AP_AD0` pin == 0
     Primary   = 0x68,
@@ -17,8 +19,43 @@ pub enum Address {
     Secondary = 0x69,
 }
 
+/// Part detected on the bus, as determined by its device ID
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DeviceVariant {
+    /// ICM-42670
+    Icm42670,
+    /// ICM-42607
+    Icm42607,
+    /// Any other part, along with the device ID it reported
+    Unknown(u8),
+}
+
+impl DeviceVariant {
+    /// Device ID reported by the part
+    pub fn device_id(&self) -> u8 {
+        match self {
+            DeviceVariant::Icm42670 => 0x67,
+            DeviceVariant::Icm42607 => 0x60,
+            DeviceVariant::Unknown(id) => *id,
+        }
+    }
+}
+
+impl From<u8> for DeviceVariant {
+    fn from(device_id: u8) -> Self {
+        match device_id {
+            0x67 => DeviceVariant::Icm42670,
+            0x60 => DeviceVariant::Icm42607,
+            id => DeviceVariant::Unknown(id),
+        }
+    }
+}
+
 /// Configurable ranges of the Accelerometer
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccelRange {
     /// ±2G
     G2  = 3,
@@ -46,11 +83,10 @@ impl AccelRange {
 }
 
 impl Bitfield for AccelRange {
-    const BITMASK: u8 = 0b0110_0000;
+    const BITMASK: u8 = field::ACCEL_UI_FS_SEL.mask();
 
     fn bits(self) -> u8 {
-        // `ACCEL_UI_FS_SEL` occupies bits 6:5 in the register
-        (self as u8) << 5
+        field::ACCEL_UI_FS_SEL.bits(self as u8)
     }
 }
 
@@ -78,6 +114,8 @@ impl TryFrom<u8> for AccelRange {
 
 /// Configurable ranges of the Gyroscope
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GyroRange {
     /// ±250 deg/sec
     Deg250  = 3,
@@ -105,11 +143,10 @@ impl GyroRange {
 }
 
 impl Bitfield for GyroRange {
-    const BITMASK: u8 = 0b0110_0000;
+    const BITMASK: u8 = field::GYRO_UI_FS_SEL.mask();
 
     fn bits(self) -> u8 {
-        // `GYRO_UI_FS_SEL` occupies bits 6:5 in the register
-        (self as u8) << 5
+        field::GYRO_UI_FS_SEL.bits(self as u8)
     }
 }
 
@@ -137,6 +174,8 @@ impl TryFrom<u8> for GyroRange {
 
 /// Configurable power modes of the IMU
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerMode {
     /// Gyroscope: OFF, Accelerometer: OFF
     Sleep           = 0b0000,
@@ -153,11 +192,10 @@ pub enum PowerMode {
 }
 
 impl Bitfield for PowerMode {
-    const BITMASK: u8 = 0b0000_1111;
+    const BITMASK: u8 = field::GYRO_MODE.mask() | field::ACCEL_MODE.mask();
 
     fn bits(self) -> u8 {
-        // `GYRO_MODE` occupies bits 3:2 in the register
-        // `ACCEL_MODE` occupies bits 1:0 in the register
+        // Each discriminant holds the values of both fields
         self as u8
     }
 }
@@ -188,6 +226,8 @@ impl TryFrom<u8> for PowerMode {
 
 /// Accelerometer ODR selection values
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccelOdr {
     /// 1.6 kHz (LN mode)
     Hz1600   = 0b0101,
@@ -231,14 +271,30 @@ impl AccelOdr {
             Hz1_5625 => 1.5625,
         }
     }
+
+    /// Can the accelerometer run at this output data rate in the given power
+    /// mode?
+    ///
+    /// The fastest rates are only available in low-noise mode, while the
+    /// slowest are only available in low-power mode.
+    pub fn supported_in(self, mode: PowerMode) -> bool {
+        use AccelOdr::*;
+
+        match mode {
+            PowerMode::AccelLowNoise | PowerMode::SixAxisLowNoise => {
+                !matches!(self, Hz6_25 | Hz3_125 | Hz1_5625)
+            }
+            PowerMode::AccelLowPower => !matches!(self, Hz1600 | Hz800),
+            _ => true,
+        }
+    }
 }
 
 impl Bitfield for AccelOdr {
-    const BITMASK: u8 = 0b0000_1111;
+    const BITMASK: u8 = field::ACCEL_ODR.mask();
 
     fn bits(self) -> u8 {
-        // `ACCEL_ODR` occupies bits 3:0 in the register
-        self as u8
+        field::ACCEL_ODR.bits(self as u8)
     }
 }
 
@@ -271,8 +327,72 @@ impl TryFrom<u8> for AccelOdr {
     }
 }
 
+/// Accelerometer low-pass filter bandwidth selection values
+///
+/// The filter is only applied in low noise mode.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccelLpf {
+    /// Low-pass filter bypassed
+    ///
+    /// Removes the filter's group delay, giving the lowest possible latency
+    /// at the cost of increased noise; intended for high-rate control loops.
+    Bypass = 0b000,
+    /// 180 Hz bandwidth
+    Hz180  = 0b001,
+    /// 121 Hz bandwidth
+    Hz121  = 0b010,
+    /// 73 Hz bandwidth
+    Hz73   = 0b011,
+    /// 53 Hz bandwidth
+    Hz53   = 0b100,
+    /// 34 Hz bandwidth
+    Hz34   = 0b101,
+    /// 25 Hz bandwidth
+    Hz25   = 0b110,
+    /// 16 Hz bandwidth
+    Hz16   = 0b111,
+}
+
+impl Bitfield for AccelLpf {
+    const BITMASK: u8 = field::ACCEL_UI_FILT_BW.mask();
+
+    fn bits(self) -> u8 {
+        field::ACCEL_UI_FILT_BW.bits(self as u8)
+    }
+}
+
+impl Default for AccelLpf {
+    fn default() -> Self {
+        Self::Hz180
+    }
+}
+
+impl TryFrom<u8> for AccelLpf {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use AccelLpf::*;
+
+        match value {
+            0b000 => Ok(Bypass),
+            0b001 => Ok(Hz180),
+            0b010 => Ok(Hz121),
+            0b011 => Ok(Hz73),
+            0b100 => Ok(Hz53),
+            0b101 => Ok(Hz34),
+            0b110 => Ok(Hz25),
+            0b111 => Ok(Hz16),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
 /// Gyroscope ODR selection values
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GyroOdr {
     /// 1.6k Hz
     Hz1600 = 0b0101,
@@ -310,11 +430,10 @@ impl GyroOdr {
 }
 
 impl Bitfield for GyroOdr {
-    const BITMASK: u8 = 0b0000_1111;
+    const BITMASK: u8 = field::GYRO_ODR.mask();
 
     fn bits(self) -> u8 {
-        // `GYRO_ODR` occupies bits 3:0 in the register
-        self as u8
+        field::GYRO_ODR.bits(self as u8)
     }
 }
 
@@ -343,3 +462,588 @@ impl TryFrom<u8> for GyroOdr {
         }
     }
 }
+
+/// Gyroscope low-pass filter bandwidth selection values
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GyroLpf {
+    /// Low-pass filter bypassed
+    ///
+    /// See [`AccelLpf::Bypass`].
+    Bypass = 0b000,
+    /// 180 Hz bandwidth
+    Hz180  = 0b001,
+    /// 121 Hz bandwidth
+    Hz121  = 0b010,
+    /// 73 Hz bandwidth
+    Hz73   = 0b011,
+    /// 53 Hz bandwidth
+    Hz53   = 0b100,
+    /// 34 Hz bandwidth
+    Hz34   = 0b101,
+    /// 25 Hz bandwidth
+    Hz25   = 0b110,
+    /// 16 Hz bandwidth
+    Hz16   = 0b111,
+}
+
+impl Bitfield for GyroLpf {
+    const BITMASK: u8 = field::GYRO_UI_FILT_BW.mask();
+
+    fn bits(self) -> u8 {
+        field::GYRO_UI_FILT_BW.bits(self as u8)
+    }
+}
+
+impl Default for GyroLpf {
+    fn default() -> Self {
+        Self::Hz180
+    }
+}
+
+impl TryFrom<u8> for GyroLpf {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use GyroLpf::*;
+
+        match value {
+            0b000 => Ok(Bypass),
+            0b001 => Ok(Hz180),
+            0b010 => Ok(Hz121),
+            0b011 => Ok(Hz73),
+            0b100 => Ok(Hz53),
+            0b101 => Ok(Hz34),
+            0b110 => Ok(Hz25),
+            0b111 => Ok(Hz16),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// FIFO operating modes
+#[cfg(feature = "fifo")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FifoMode {
+    /// Continue writing to the FIFO once it is full, replacing the oldest data
+    Stream     = 0,
+    /// Stop writing to the FIFO once it is full, dropping any new data
+    StopOnFull = 1,
+}
+
+#[cfg(feature = "fifo")]
+impl Bitfield for FifoMode {
+    const BITMASK: u8 = field::FIFO_MODE.mask();
+
+    fn bits(self) -> u8 {
+        field::FIFO_MODE.bits(self as u8)
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl Default for FifoMode {
+    fn default() -> Self {
+        Self::Stream
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl TryFrom<u8> for FifoMode {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use FifoMode::*;
+
+        match value {
+            0 => Ok(Stream),
+            1 => Ok(StopOnFull),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// FIFO bypass selection values
+#[cfg(feature = "fifo")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FifoBypass {
+    /// FIFO is enabled and sensor data is written to it
+    Enabled  = 0,
+    /// FIFO is bypassed, and no sensor data is written to it
+    Bypassed = 1,
+}
+
+#[cfg(feature = "fifo")]
+impl Bitfield for FifoBypass {
+    const BITMASK: u8 = field::FIFO_BYPASS.mask();
+
+    fn bits(self) -> u8 {
+        field::FIFO_BYPASS.bits(self as u8)
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl Default for FifoBypass {
+    fn default() -> Self {
+        Self::Bypassed
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl TryFrom<u8> for FifoBypass {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use FifoBypass::*;
+
+        match value {
+            0 => Ok(Enabled),
+            1 => Ok(Bypassed),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Functions which may be assigned to pin 9
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pin9Function {
+    /// Interrupt output `INT2`
+    Int2  = 0b00,
+    /// Frame synchronization input `FSYNC`
+    ///
+    /// See [`Icm42670::enable_fsync`](crate::Icm42670::enable_fsync).
+    Fsync = 0b01,
+    /// External clock input `CLKIN`
+    ///
+    /// RTC mode must also be enabled for the external clock to be used; see
+    /// [`Icm42670::enable_clkin`](crate::Icm42670::enable_clkin).
+    Clkin = 0b10,
+}
+
+impl Bitfield for Pin9Function {
+    const BITMASK: u8 = field::PIN9_FUNCTION.mask();
+
+    fn bits(self) -> u8 {
+        field::PIN9_FUNCTION.bits(self as u8)
+    }
+}
+
+impl Default for Pin9Function {
+    fn default() -> Self {
+        Self::Int2
+    }
+}
+
+impl TryFrom<u8> for Pin9Function {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use Pin9Function::*;
+
+        match value {
+            0b00 => Ok(Int2),
+            0b01 => Ok(Fsync),
+            0b10 => Ok(Clkin),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Interrupt output pins of the IMU
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterruptPin {
+    /// Pin `INT1`
+    Int1,
+    /// Pin `INT2`
+    Int2,
+}
+
+/// Units in which the FIFO count is reported
+#[cfg(feature = "fifo")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FifoCountFormat {
+    /// FIFO count is reported in bytes
+    Bytes   = 0,
+    /// FIFO count is reported in records (packets)
+    Records = 1,
+}
+
+#[cfg(feature = "fifo")]
+impl Bitfield for FifoCountFormat {
+    const BITMASK: u8 = field::FIFO_COUNT_FORMAT.mask();
+
+    fn bits(self) -> u8 {
+        field::FIFO_COUNT_FORMAT.bits(self as u8)
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl Default for FifoCountFormat {
+    fn default() -> Self {
+        Self::Bytes
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl TryFrom<u8> for FifoCountFormat {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use FifoCountFormat::*;
+
+        match value {
+            0 => Ok(Bytes),
+            1 => Ok(Records),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Byte order in which the FIFO count is reported
+#[cfg(feature = "fifo")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FifoCountEndian {
+    /// FIFO count is reported in little endian format
+    Little = 0,
+    /// FIFO count is reported in big endian format
+    Big    = 1,
+}
+
+#[cfg(feature = "fifo")]
+impl Bitfield for FifoCountEndian {
+    const BITMASK: u8 = field::FIFO_COUNT_ENDIAN.mask();
+
+    fn bits(self) -> u8 {
+        field::FIFO_COUNT_ENDIAN.bits(self as u8)
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl Default for FifoCountEndian {
+    fn default() -> Self {
+        Self::Big
+    }
+}
+
+#[cfg(feature = "fifo")]
+impl TryFrom<u8> for FifoCountEndian {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use FifoCountEndian::*;
+
+        match value {
+            0 => Ok(Little),
+            1 => Ok(Big),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Byte order in which sensor data is reported
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SensorDataEndian {
+    /// Sensor data is reported in little endian format
+    Little = 0,
+    /// Sensor data is reported in big endian format
+    Big    = 1,
+}
+
+impl SensorDataEndian {
+    /// Combine two bytes, in the order in which they were read, into a single
+    /// value
+    pub(crate) fn word(self, bytes: [u8; 2]) -> i16 {
+        match self {
+            SensorDataEndian::Little => i16::from_le_bytes(bytes),
+            SensorDataEndian::Big => i16::from_be_bytes(bytes),
+        }
+    }
+}
+
+impl Bitfield for SensorDataEndian {
+    const BITMASK: u8 = field::SENSOR_DATA_ENDIAN.mask();
+
+    fn bits(self) -> u8 {
+        field::SENSOR_DATA_ENDIAN.bits(self as u8)
+    }
+}
+
+impl Default for SensorDataEndian {
+    fn default() -> Self {
+        Self::Big
+    }
+}
+
+impl TryFrom<u8> for SensorDataEndian {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use SensorDataEndian::*;
+
+        match value {
+            0 => Ok(Little),
+            1 => Ok(Big),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Interrupt pin signalling modes
+///
+/// In latched mode the conditions under which an interrupt is cleared are
+/// selected by [`InterruptClear`]; an interrupt which is cleared before it
+/// has been handled is lost.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterruptMode {
+    /// The interrupt pin is asserted for a short pulse
+    Pulsed  = 0,
+    /// The interrupt pin remains asserted until the interrupt is cleared
+    Latched = 1,
+}
+
+impl Default for InterruptMode {
+    fn default() -> Self {
+        Self::Pulsed
+    }
+}
+
+impl TryFrom<u8> for InterruptMode {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use InterruptMode::*;
+
+        match value {
+            0 => Ok(Pulsed),
+            1 => Ok(Latched),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Interrupt pin output drive circuits
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterruptDrive {
+    /// Open drain output, requiring an external pull-up
+    OpenDrain = 0,
+    /// Push-pull output
+    PushPull  = 1,
+}
+
+impl Default for InterruptDrive {
+    fn default() -> Self {
+        Self::OpenDrain
+    }
+}
+
+impl TryFrom<u8> for InterruptDrive {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use InterruptDrive::*;
+
+        match value {
+            0 => Ok(OpenDrain),
+            1 => Ok(PushPull),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Interrupt pin output polarities
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterruptPolarity {
+    /// The interrupt pin is driven low when asserted
+    ActiveLow  = 0,
+    /// The interrupt pin is driven high when asserted
+    ActiveHigh = 1,
+}
+
+impl Default for InterruptPolarity {
+    fn default() -> Self {
+        Self::ActiveLow
+    }
+}
+
+impl TryFrom<u8> for InterruptPolarity {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use InterruptPolarity::*;
+
+        match value {
+            0 => Ok(ActiveLow),
+            1 => Ok(ActiveHigh),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Conditions under which a latched interrupt is cleared
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InterruptClear {
+    /// Cleared when its status bit is read
+    StatusRead       = 0b00,
+    /// Cleared when the associated data is read; the sensor data registers
+    /// for data ready, or a byte of `FIFO_DATA` for the FIFO interrupts
+    DataRead         = 0b10,
+    /// Cleared when either its status bit or the associated data is read
+    StatusOrDataRead = 0b11,
+}
+
+impl Default for InterruptClear {
+    fn default() -> Self {
+        Self::StatusRead
+    }
+}
+
+impl TryFrom<u8> for InterruptClear {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use InterruptClear::*;
+
+        match value {
+            0b00 | 0b01 => Ok(StatusRead),
+            0b10 => Ok(DataRead),
+            0b11 => Ok(StatusOrDataRead),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// APEX features which have been enabled via the driver
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ApexFeatures {
+    /// Pedometer is enabled
+    pub pedometer: bool,
+    /// Tilt detection is enabled
+    pub tilt: bool,
+    /// Significant Motion Detection is enabled
+    pub smd: bool,
+    /// Freefall detection is enabled
+    pub freefall: bool,
+    /// Wake on Motion is enabled
+    pub wake_on_motion: bool,
+}
+
+impl ApexFeatures {
+    /// Verify that the enabled features are able to run concurrently, and that
+    /// none is missing a feature on which it depends
+    ///
+    /// The driver checks this whenever a feature is enabled or disabled.
+    pub fn validate(&self) -> Result<(), SensorError> {
+        if self.freefall && (self.pedometer || self.tilt || self.smd) {
+            Err(SensorError::IncompatibleApexFeatures)
+        } else if self.smd && !self.wake_on_motion {
+            Err(SensorError::WakeOnMotionRequired)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Ranges and output data rates retained by the driver once read or set, so
+/// that normalizing a sample does not require reading them from the device
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct CachedConfig {
+    pub accel_range: Option<AccelRange>,
+    pub gyro_range: Option<GyroRange>,
+    pub accel_odr: Option<AccelOdr>,
+    pub gyro_odr: Option<GyroOdr>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wake_on_motion_cannot_be_disabled_before_smd() {
+        let enabled = ApexFeatures {
+            smd: true,
+            wake_on_motion: true,
+            ..ApexFeatures::default()
+        };
+        assert!(enabled.validate().is_ok());
+
+        let without_wom = ApexFeatures {
+            wake_on_motion: false,
+            ..enabled
+        };
+        assert!(matches!(
+            without_wom.validate(),
+            Err(SensorError::WakeOnMotionRequired)
+        ));
+
+        let without_smd = ApexFeatures {
+            smd: false,
+            ..enabled
+        };
+        assert!(without_smd.validate().is_ok());
+        assert!(ApexFeatures {
+            wake_on_motion: false,
+            ..without_smd
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn disabling_a_feature_checks_dependencies() {
+        let enabled = ApexFeatures {
+            pedometer: true,
+            tilt: true,
+            smd: true,
+            freefall: false,
+            wake_on_motion: true,
+        };
+        assert!(enabled.validate().is_ok());
+
+        for features in [
+            ApexFeatures {
+                pedometer: false,
+                ..enabled
+            },
+            ApexFeatures {
+                tilt: false,
+                ..enabled
+            },
+            ApexFeatures {
+                smd: false,
+                ..enabled
+            },
+        ] {
+            assert!(features.validate().is_ok());
+        }
+
+        assert!(ApexFeatures {
+            wake_on_motion: false,
+            ..enabled
+        }
+        .validate()
+        .is_err());
+    }
+}
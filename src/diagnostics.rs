@@ -0,0 +1,53 @@
+//! Diagnostic utilities for field debugging
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    mode::{MregAccessible, PowerState},
+    register::{Bank0, Mreg1, Mreg2, Mreg3, Register, RegisterBank},
+    Bank,
+    Error,
+    Icm42670,
+    Interface,
+};
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Read every known register in bank 0 and the MREG banks, reporting the
+    /// bank, address and value of each to the provided callback
+    ///
+    /// Registers are reported in order of bank and then address. Registers
+    /// which cannot be read without side effects, such as the interrupt
+    /// status registers which are cleared by reading them and `FIFO_DATA`, are
+    /// skipped, so dumping the registers does not disturb the device.
+    pub fn dump_registers<D, F>(&mut self, delay: &mut D, mut f: F) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        F: FnMut(Bank, u8, u8),
+        MODE: MregAccessible,
+    {
+        for reg in Bank0::DUMPABLE {
+            f(Bank::Bank0, reg.addr(), self.read_reg(&reg)?);
+        }
+        for reg in Mreg1::ALL {
+            let value = self.read_mreg(delay, RegisterBank::MReg1, &reg)?;
+            f(Bank::MReg1, reg.addr(), value);
+        }
+        for reg in Mreg2::ALL {
+            let value = self.read_mreg(delay, RegisterBank::MReg2, &reg)?;
+            f(Bank::MReg2, reg.addr(), value);
+        }
+        for reg in Mreg3::ALL {
+            let value = self.read_mreg(delay, RegisterBank::MReg3, &reg)?;
+            f(Bank::MReg3, reg.addr(), value);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Burst reads performed outside of the driver
+//!
+//! Large reads, such as draining the FIFO, may be handed off to a DMA
+//! controller rather than performed by the driver. A [`BurstRead`] describes
+//! such a read: which register to start from and how many bytes to read. Once
+//! the transfer has completed, the data is decoded using
+//! [`Icm42670::decode_sensor_data`] or [`FifoFrames`](crate::FifoFrames).
+
+use core::fmt::Debug;
+
+#[cfg(feature = "fifo")]
+use crate::{error::SensorError, register::Register, Error, FifoCount};
+use crate::{interface::SPI_READ, mode::PowerState, register::Bank0, Icm42670, Interface, RawData};
+
+/// Description of a burst read of consecutive registers
+///
+/// Over I²C the transfer consists of a write of [`BurstRead::addr`] followed
+/// by a repeated start and a read of [`BurstRead::len`] bytes. Over SPI the
+/// transfer consists of [`BurstRead::spi_command`] followed by `len` bytes
+/// clocked out of the device, all with the chip select pin asserted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BurstRead {
+    /// Address of the first register to read
+    pub addr: u8,
+    /// Number of bytes to read
+    pub len: usize,
+}
+
+impl BurstRead {
+    /// All of the sensor data registers, from `TEMP_DATA1` through
+    /// `TMST_FSYNCL`, as read by [`Icm42670::read_raw`]
+    ///
+    /// The 16 bytes read are decoded by [`Icm42670::decode_sensor_data`].
+    pub const SENSOR_DATA: BurstRead = BurstRead {
+        addr: Bank0::TEMP_DATA1 as u8,
+        len: 16,
+    };
+
+    /// First byte of the transfer over SPI, which selects the register to read
+    pub fn spi_command(&self) -> u8 {
+        self.addr | SPI_READ
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Decode sensor data read using [`BurstRead::SENSOR_DATA`]
+    ///
+    /// The data is interpreted using the sensor data byte order retained by
+    /// the driver, exactly as by [`Icm42670::read_raw`].
+    pub fn decode_sensor_data(&self, bytes: &[u8; 16]) -> RawData {
+        RawData::from_bytes(bytes, self.data_endian)
+    }
+
+    /// Describe a burst read which drains the data currently held in the FIFO
+    ///
+    /// `FIFO_DATA` does not auto-increment the register address, so the
+    /// entire FIFO is drained in a single transfer, the contents of which are
+    /// decoded using [`FifoFrames`](crate::FifoFrames) along with the byte
+    /// order returned by [`Icm42670::sensor_data_endian`]. Returns `None` if
+    /// the FIFO is empty.
+    ///
+    /// This requires the FIFO count to be reported in bytes; if it is
+    /// configured to use [`FifoCountFormat::Records`](crate::FifoCountFormat)
+    /// then an error is returned.
+    #[cfg(feature = "fifo")]
+    pub fn fifo_burst(&mut self) -> Result<Option<BurstRead>, Error<E>> {
+        let len = match self.fifo_count()? {
+            FifoCount::Bytes(0) => return Ok(None),
+            FifoCount::Bytes(count) => count as usize,
+            FifoCount::Records(_) => {
+                return Err(Error::SensorError(SensorError::UnexpectedFifoCountFormat))
+            }
+        };
+
+        // The caller is expected to perform the transfer, which clears any FIFO
+        // interrupts configured to clear on data read
+        self.fifo_data_read();
+
+        Ok(Some(BurstRead {
+            addr: Bank0::FIFO_DATA.addr(),
+            len,
+        }))
+    }
+}
@@ -1,5 +1,16 @@
+// `core::error::Error` is only available as of Rust 1.81, so
+// `std::error::Error` is implemented instead when only the `std` feature is
+// enabled. They are the same trait on toolchains where both are available, so
+// only one may be implemented.
+#[cfg(feature = "core-error")]
+pub(crate) use core::error::Error as StdError;
+use core::fmt::{self, Debug, Display};
+#[cfg(all(feature = "std", not(feature = "core-error")))]
+pub(crate) use std::error::Error as StdError;
+
 /// Any type of error which may occur while interacting with the device
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error<E> {
     /// Some error originating from the communication bus
     BusError(E),
@@ -9,13 +20,14 @@ pub enum Error<E> {
 
 /// Any type of error specific to this device
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SensorError {
     /// The chip at the specified address is not reporting the correct self
-    /// identification code.
+    /// identification code, which is provided.
     ///
     /// For I²C this is most likely if the ID change jumper is in the wrong
     /// state or there is anther chip on the bus with this address.
-    BadChip,
+    UnexpectedDeviceId(u8),
     /// Returned if the register bank is set to a invalid value
     ///
     /// There are 4 banks, 0-3
@@ -25,6 +37,68 @@ pub enum SensorError {
     /// Attempted to create an AccelRange or GyroRange enum from an invalid
     /// discriminant
     InvalidDiscriminant,
+    /// Attempted to set a FIFO watermark of zero, or one which does not fit
+    /// within the 12-bit watermark field
+    InvalidWatermark,
+    /// The FIFO count was reported in records where a count in bytes was
+    /// required
+    UnexpectedFifoCountFormat,
+    /// Attempted to configure a threshold which cannot be represented by the
+    /// device
+    ThresholdOutOfRange,
+    /// Attempted to configure a duration which cannot be represented by the
+    /// device
+    DurationOutOfRange,
+    /// The DMP output data rate is faster than that of the accelerometer
+    DmpOdrMismatch,
+    /// Attempted to enable an APEX feature which cannot run concurrently with
+    /// one which is already enabled
+    ///
+    /// Freefall detection requires a DMP output data rate which is
+    /// incompatible with the pedometer, tilt detection and SMD.
+    IncompatibleApexFeatures,
+    /// Attempted to enable an APEX feature which depends on Wake on Motion
+    /// while Wake on Motion is disabled
+    WakeOnMotionRequired,
+    /// Attempted to set a user offset which does not fit within the 12-bit
+    /// offset field
+    OffsetOutOfRange,
+    /// Calibration measurements are inconsistent with the orientations in
+    /// which they were recorded
+    InvalidCalibration,
+    /// A calibration measurement has not been recorded in every orientation
+    MissingCalibrationMeasurement,
+    /// Serialized calibration data has the wrong length or version, or its
+    /// checksum does not match
+    CorruptCalibration,
+    /// Attempted to collect zero samples
+    InvalidSampleCount,
+    /// The device reported an invalid sample, for example because the sensor
+    /// is disabled or has not yet started up
+    InvalidSample,
+    /// Attempted to learn a temperature compensation point while the table is
+    /// full
+    TemperatureTableFull,
+    /// Attempted to learn a temperature compensation point at a temperature
+    /// which is not finite
+    InvalidTemperature,
+    /// The accelerometer output data rate is unavailable in the configured
+    /// power mode
+    OdrUnsupportedInPowerMode,
+    /// The device did not report that a software reset completed
+    ResetTimeout,
+    /// No device responded on the bus, even after retrying
+    NoDeviceResponding,
+    /// The internal clock did not become ready for MREG access
+    MclkTimeout,
+    /// No new sample became available within two output data periods
+    DataReadyTimeout,
+    /// The device did not report that a FIFO flush completed
+    FifoFlushTimeout,
+    /// Packets were lost due to the FIFO overflowing
+    FifoOverflow,
+    /// A sensor axis responded to self-test outside of the acceptance window
+    SelfTestFailed,
 }
 
 impl<E> From<SensorError> for Error<E> {
@@ -32,3 +106,61 @@ impl<E> From<SensorError> for Error<E> {
         Error::SensorError(err)
     }
 }
+
+impl<E> Display for Error<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BusError(e) => write!(f, "bus error: {:?}", e),
+            Error::SensorError(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl<E> StdError for Error<E> where E: Debug {}
+
+impl Display for SensorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use SensorError::*;
+
+        let msg = match self {
+            UnexpectedDeviceId(id) => return write!(f, "unexpected device ID {:#04x}", id),
+            BankOutOfRange => "register bank out of range",
+            WriteToReadOnly => "attempted to write to a read-only register",
+            InvalidDiscriminant => "invalid discriminant",
+            InvalidWatermark => "FIFO watermark out of range",
+            UnexpectedFifoCountFormat => "FIFO count reported in records rather than bytes",
+            ThresholdOutOfRange => "threshold out of range",
+            DurationOutOfRange => "duration out of range",
+            DmpOdrMismatch => "DMP output data rate faster than that of the accelerometer",
+            IncompatibleApexFeatures => "APEX feature cannot run alongside those already enabled",
+            WakeOnMotionRequired => "APEX feature requires Wake on Motion",
+            OffsetOutOfRange => "user offset out of range",
+            InvalidCalibration => "calibration measurements inconsistent",
+            MissingCalibrationMeasurement => "calibration measurement missing",
+            CorruptCalibration => "serialized calibration corrupt",
+            InvalidSampleCount => "sample count must be non-zero",
+            InvalidSample => "device reported an invalid sample",
+            TemperatureTableFull => "temperature compensation table full",
+            InvalidTemperature => "temperature not finite",
+            OdrUnsupportedInPowerMode => {
+                "accelerometer output data rate unsupported in the power mode"
+            }
+            ResetTimeout => "software reset did not complete",
+            NoDeviceResponding => "no device responding",
+            MclkTimeout => "internal clock not ready",
+            DataReadyTimeout => "no new sample available",
+            FifoFlushTimeout => "FIFO flush did not complete",
+            FifoOverflow => "FIFO overflowed",
+            SelfTestFailed => "self-test failed",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl StdError for SensorError {}
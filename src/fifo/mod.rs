@@ -0,0 +1,490 @@
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+pub use self::packet::{FifoFrames, Frame, Packet};
+use crate::{
+    config::{Bitfield, FifoBypass, FifoCountEndian, FifoCountFormat, FifoMode, InterruptPin},
+    error::SensorError,
+    mode::{Dynamic, MregAccessible, PowerState},
+    register::{field, Bank0, Mreg1, RegisterBank},
+    Error,
+    Icm42670,
+    Interface,
+    InterruptSource,
+};
+
+mod packet;
+
+/// Size of the FIFO, in bytes
+pub(crate) const FIFO_SIZE: u16 = 2304;
+
+/// Packet holding no data, used to fill the unused entries of an array
+const EMPTY_PACKET: Packet = Packet {
+    accel: None,
+    gyro: None,
+    temperature: 0,
+    timestamp: None,
+    fsync: false,
+};
+
+/// FIFO configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FifoConfig {
+    /// Behaviour of the FIFO once it has filled up
+    pub mode: FifoMode,
+    /// Data which is written to the FIFO
+    pub content: FifoContent,
+}
+
+/// Selection of the data which is written to the FIFO
+///
+/// Temperature data is always written to the FIFO alongside any sensor data.
+/// The type of packet which is written, and therefore its size, depends on
+/// the selected content:
+///
+/// | Content                      | Packet size |
+/// | ---------------------------- | ----------- |
+/// | Accelerometer only           | 8 bytes     |
+/// | Gyroscope only               | 8 bytes     |
+/// | Accelerometer and gyroscope  | 16 bytes    |
+/// | High resolution              | 20 bytes    |
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FifoContent {
+    /// Write accelerometer data to the FIFO
+    pub accel: bool,
+    /// Write gyroscope data to the FIFO
+    pub gyro: bool,
+    /// Write the timestamp, or the FSYNC time when FSYNC is in use, to the FIFO
+    ///
+    /// This is only available in 16- and 20-byte packets.
+    pub timestamp_fsync: bool,
+    /// Write 20-bit accelerometer and gyroscope data and 16-bit temperature
+    /// data to the FIFO, using 20-byte packets
+    pub high_resolution: bool,
+}
+
+impl FifoContent {
+    pub(crate) const BITMASK: u8 = field::FIFO_HIRES_EN.mask()
+        | field::FIFO_TMST_FSYNC_EN.mask()
+        | field::FIFO_GYRO_EN.mask()
+        | field::FIFO_ACCEL_EN.mask();
+
+    pub(crate) fn bits(self) -> u8 {
+        field::FIFO_HIRES_EN.bits(self.high_resolution as u8)
+            | field::FIFO_TMST_FSYNC_EN.bits(self.timestamp_fsync as u8)
+            | field::FIFO_GYRO_EN.bits(self.gyro as u8)
+            | field::FIFO_ACCEL_EN.bits(self.accel as u8)
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        Self {
+            accel: field::FIFO_ACCEL_EN.get(bits) != 0,
+            gyro: field::FIFO_GYRO_EN.get(bits) != 0,
+            timestamp_fsync: field::FIFO_TMST_FSYNC_EN.get(bits) != 0,
+            high_resolution: field::FIFO_HIRES_EN.get(bits) != 0,
+        }
+    }
+}
+
+/// Number of bytes or records held in the FIFO, depending on the configured
+/// [`FifoCountFormat`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FifoCount {
+    /// FIFO count in bytes
+    Bytes(u16),
+    /// FIFO count in records (packets)
+    Records(u16),
+}
+
+/// Iterator over the packets held in the FIFO
+///
+/// Created by [`Icm42670::fifo_packets`]. Packets are read from the device on
+/// demand until the FIFO has been drained of the data present when the
+/// iterator was created.
+pub struct FifoPackets<'a, DI, MODE = Dynamic> {
+    imu: &'a mut Icm42670<DI, MODE>,
+    remaining: FifoCount,
+}
+
+impl<'a, DI, E, MODE> Iterator for FifoPackets<'a, DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    type Item = Result<Packet, Error<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.remaining {
+            FifoCount::Bytes(0) | FifoCount::Records(0) => return None,
+            _ => {}
+        }
+
+        let mut buffer = [0u8; 20];
+        if let Err(e) = self.imu.read_fifo(&mut buffer[..1]) {
+            return Some(Err(e));
+        }
+
+        let len = match Frame::len(buffer[0]) {
+            Some(len) => len,
+            None => {
+                // Either the FIFO is empty or the data is not framed as expected;
+                // either way no further packets can be decoded.
+                self.remaining = FifoCount::Bytes(0);
+                return None;
+            }
+        };
+
+        if let Err(e) = self.imu.read_fifo(&mut buffer[1..len]) {
+            return Some(Err(e));
+        }
+
+        self.remaining = match self.remaining {
+            FifoCount::Bytes(n) => FifoCount::Bytes(n.saturating_sub(len as u16)),
+            FifoCount::Records(n) => FifoCount::Records(n - 1),
+        };
+
+        Some(Ok(Frame::new(&buffer[..len], self.imu.data_endian).packet()))
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Enable the FIFO, causing sensor data to be written to it
+    pub fn enable_fifo(&mut self) -> Result<(), Error<E>> {
+        self.update_reg(
+            &Bank0::FIFO_CONFIG1,
+            FifoBypass::Enabled.bits(),
+            FifoBypass::BITMASK,
+        )
+    }
+
+    /// Disable the FIFO, causing it to be bypassed
+    pub fn disable_fifo(&mut self) -> Result<(), Error<E>> {
+        self.update_reg(
+            &Bank0::FIFO_CONFIG1,
+            FifoBypass::Bypassed.bits(),
+            FifoBypass::BITMASK,
+        )
+    }
+
+    /// Configure the FIFO's operating mode and the data which is written to it
+    ///
+    /// This does not enable the FIFO; see [`Icm42670::enable_fifo`].
+    pub fn configure_fifo<D>(&mut self, delay: &mut D, config: FifoConfig) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.set_fifo_content(delay, config.content)?;
+        self.set_fifo_mode(config.mode)
+    }
+
+    /// Return the currently selected FIFO content
+    pub fn fifo_content<D>(&mut self, delay: &mut D) -> Result<FifoContent, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let bits = self.read_mreg(delay, RegisterBank::MReg1, &Mreg1::FIFO_CONFIG5)?;
+
+        Ok(FifoContent::from_bits(bits))
+    }
+
+    /// Select which data is written to the FIFO
+    pub fn set_fifo_content<D>(
+        &mut self,
+        delay: &mut D,
+        content: FifoContent,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::FIFO_CONFIG5,
+            content.bits(),
+            FifoContent::BITMASK,
+        )
+    }
+
+    /// Return the currently configured FIFO mode
+    pub fn fifo_mode(&mut self) -> Result<FifoMode, Error<E>> {
+        let bits = self.read_field(field::FIFO_MODE)?;
+        let mode = FifoMode::try_from(bits)?;
+
+        Ok(mode)
+    }
+
+    /// Set the FIFO mode, determining its behaviour once it has filled up
+    ///
+    /// In [`FifoMode::Stream`] the oldest data is discarded to make room for
+    /// new data, so the FIFO always holds the most recent samples; however, if
+    /// the host is reading from the FIFO as it overflows, the packet currently
+    /// being read may be corrupted. In [`FifoMode::StopOnFull`] any new data is
+    /// dropped once the FIFO is full, guaranteeing that the data it holds is
+    /// intact at the expense of losing the most recent samples.
+    ///
+    /// The FIFO should be flushed after changing modes, as any data held in it
+    /// may otherwise be inconsistent with the new mode.
+    pub fn set_fifo_mode(&mut self, mode: FifoMode) -> Result<(), Error<E>> {
+        self.update_reg(&Bank0::FIFO_CONFIG1, mode.bits(), FifoMode::BITMASK)
+    }
+
+    /// Return the currently configured FIFO watermark
+    pub fn fifo_watermark(&mut self) -> Result<u16, Error<E>> {
+        // `FIFO_WM` is split across two registers, with `FIFO_CONFIG3` holding bits
+        // 11:8 and `FIFO_CONFIG2` holding bits 7:0
+        let hi = self.read_field(field::FIFO_WM_H)?;
+        let lo = self.read_field(field::FIFO_WM_L)?;
+
+        Ok(u16::from_be_bytes([hi, lo]))
+    }
+
+    /// Set the FIFO watermark
+    ///
+    /// Once the amount of data in the FIFO reaches the watermark the FIFO
+    /// threshold interrupt is raised. The watermark is interpreted in either
+    /// bytes or records, depending on the configured [`FifoCountFormat`], and
+    /// must be non-zero and fit within 12 bits.
+    pub fn set_fifo_watermark(&mut self, watermark: u16) -> Result<(), Error<E>> {
+        if watermark == 0 || watermark > 0x0FFF {
+            return Err(Error::SensorError(SensorError::InvalidWatermark));
+        }
+
+        let [hi, lo] = watermark.to_be_bytes();
+        self.write_reg(&Bank0::FIFO_CONFIG2, lo)?;
+        self.write_field(field::FIFO_WM_H, hi)
+    }
+
+    /// Route the FIFO threshold interrupt to the given interrupt pin
+    pub fn enable_fifo_threshold_interrupt(&mut self, pin: InterruptPin) -> Result<(), Error<E>> {
+        self.enable_interrupt(InterruptSource::FifoThreshold, pin)
+    }
+
+    /// Stop routing the FIFO threshold interrupt to the given interrupt pin
+    pub fn disable_fifo_threshold_interrupt(&mut self, pin: InterruptPin) -> Result<(), Error<E>> {
+        self.disable_interrupt(InterruptSource::FifoThreshold, pin)
+    }
+
+    /// Flush the FIFO, discarding any data which it currently holds
+    ///
+    /// Rather than hanging should the flush never complete, give up after
+    /// roughly 1ms.
+    pub fn fifo_flush<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        self.write_field(field::FIFO_FLUSH, 1)?;
+
+        // The flush bit is cleared by the device once the flush has completed, and
+        // the FIFO must not be accessed before this happens.
+        for _ in 0..100 {
+            delay.delay_us(10);
+            if self.read_field(field::FIFO_FLUSH)? == 0 {
+                return Ok(());
+            }
+        }
+
+        Err(Error::SensorError(SensorError::FifoFlushTimeout))
+    }
+
+    /// Return the currently configured FIFO count format
+    pub fn fifo_count_format(&mut self) -> Result<FifoCountFormat, Error<E>> {
+        let bits = self.read_field(field::FIFO_COUNT_FORMAT)?;
+        let format = FifoCountFormat::try_from(bits)?;
+
+        Ok(format)
+    }
+
+    /// Set whether the FIFO count is reported in bytes or records
+    pub fn set_fifo_count_format(&mut self, format: FifoCountFormat) -> Result<(), Error<E>> {
+        self.update_reg(
+            &Bank0::INTF_CONFIG0,
+            format.bits(),
+            FifoCountFormat::BITMASK,
+        )
+    }
+
+    /// Return the currently configured FIFO count byte order
+    pub fn fifo_count_endian(&mut self) -> Result<FifoCountEndian, Error<E>> {
+        let bits = self.read_field(field::FIFO_COUNT_ENDIAN)?;
+        let endian = FifoCountEndian::try_from(bits)?;
+
+        Ok(endian)
+    }
+
+    /// Set the byte order in which the FIFO count is reported
+    pub fn set_fifo_count_endian(&mut self, endian: FifoCountEndian) -> Result<(), Error<E>> {
+        self.update_reg(
+            &Bank0::INTF_CONFIG0,
+            endian.bits(),
+            FifoCountEndian::BITMASK,
+        )
+    }
+
+    /// Check whether the FIFO has filled up since it was last
+    /// reported
+    pub fn fifo_full(&mut self) -> Result<bool, Error<E>> {
+        let full = self.take_interrupt(field::FIFO_FULL_INT)?;
+
+        Ok(full)
+    }
+
+    /// Read the number of packets which have been lost due to the FIFO
+    /// overflowing
+    ///
+    /// In [`FifoMode::Stream`] this is the number of packets which were
+    /// overwritten, and in [`FifoMode::StopOnFull`] it is the number of packets
+    /// which were dropped. The count is reset when the FIFO is flushed.
+    pub fn fifo_lost_packets(&mut self) -> Result<u16, Error<E>> {
+        // `FIFO_LOST_PKT0` holds the low byte and `FIFO_LOST_PKT1` the high byte of
+        // the count
+        let mut buffer = [0u8; 2];
+        self.read_regs(&Bank0::FIFO_LOST_PKT0, &mut buffer)?;
+
+        Ok(u16::from_le_bytes(buffer))
+    }
+
+    /// Return [`SensorError::FifoOverflow`] if any packets have been lost due
+    /// to the FIFO overflowing since it was last flushed
+    ///
+    /// Unlike [`Icm42670::fifo_full`], this does not read the interrupt status,
+    /// so no interrupt flags are cleared. Losing packets is expected in
+    /// [`FifoMode::Stream`] whenever the host falls behind, so whether this is
+    /// an error is left to the caller; the FIFO read methods never call it.
+    pub fn check_fifo_overflow(&mut self) -> Result<(), Error<E>> {
+        if self.fifo_lost_packets()? != 0 {
+            return Err(Error::SensorError(SensorError::FifoOverflow));
+        }
+
+        Ok(())
+    }
+
+    /// Recover from a FIFO overflow, returning the number of packets which
+    /// were lost
+    ///
+    /// The FIFO is flushed, discarding any data which it holds, after which
+    /// the device resumes writing new samples to it. This allows long-running
+    /// applications to continue logging after the host has stalled for long
+    /// enough for the FIFO to fill up.
+    pub fn recover_from_overflow<D>(&mut self, delay: &mut D) -> Result<u16, Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        // The lost packet count is reset by the flush, so it must be read first.
+        let lost = self.fifo_lost_packets()?;
+        self.fifo_flush(delay)?;
+
+        Ok(lost)
+    }
+
+    /// Read the amount of data currently held in the FIFO
+    ///
+    /// The count is interpreted according to the configured
+    /// [`FifoCountFormat`] and [`FifoCountEndian`].
+    pub fn fifo_count(&mut self) -> Result<FifoCount, Error<E>> {
+        let intf_config = self.read_reg(&Bank0::INTF_CONFIG0)?;
+        let format = FifoCountFormat::try_from(field::FIFO_COUNT_FORMAT.get(intf_config))?;
+        let endian = FifoCountEndian::try_from(field::FIFO_COUNT_ENDIAN.get(intf_config))?;
+
+        // The count must be read from `FIFO_COUNTH` first, which latches the value
+        // of `FIFO_COUNTL`; the registers are always read in this order regardless
+        // of which holds the most significant byte.
+        let mut buffer = [0u8; 2];
+        self.read_regs(&Bank0::FIFO_COUNTH, &mut buffer)?;
+
+        let count = match endian {
+            FifoCountEndian::Little => u16::from_le_bytes(buffer),
+            FifoCountEndian::Big => u16::from_be_bytes(buffer),
+        };
+
+        let count = match format {
+            FifoCountFormat::Bytes => FifoCount::Bytes(count),
+            FifoCountFormat::Records => FifoCount::Records(count),
+        };
+
+        Ok(count)
+    }
+
+    /// Return an iterator over the packets currently held in the FIFO
+    pub fn fifo_packets(&mut self) -> Result<FifoPackets<'_, DI, MODE>, Error<E>> {
+        let remaining = self.fifo_count()?;
+
+        Ok(FifoPackets {
+            imu: self,
+            remaining,
+        })
+    }
+
+    /// Read up to `N` packets from the FIFO, returning them along with the
+    /// number of packets read
+    ///
+    /// No more than `N` packets are read, even if the FIFO holds more, so with
+    /// `N` matched to the configured watermark an interrupt handler can drain
+    /// exactly one watermark's worth of packets without allocating. Entries
+    /// following those which were read hold no data.
+    pub fn read_fifo_frames<const N: usize>(&mut self) -> Result<([Packet; N], usize), Error<E>> {
+        let mut packets = [EMPTY_PACKET; N];
+        let mut count = 0;
+
+        for (slot, packet) in packets.iter_mut().zip(self.fifo_packets()?) {
+            *slot = packet?;
+            count += 1;
+        }
+
+        Ok((packets, count))
+    }
+
+    /// Read as much data from the FIFO as is available and fits within the
+    /// provided buffer, returning the number of bytes read
+    ///
+    /// The data is read in a single burst transaction. If the buffer is smaller
+    /// than the amount of data held in the FIFO a partial packet may be read,
+    /// so the buffer should be sized to hold a whole number of packets.
+    ///
+    /// This requires the FIFO count to be reported in bytes; if it is
+    /// configured to use [`FifoCountFormat::Records`] then an error is
+    /// returned.
+    pub fn read_fifo_bytes(&mut self, buffer: &mut [u8]) -> Result<usize, Error<E>> {
+        let count = match self.fifo_count()? {
+            FifoCount::Bytes(count) => count as usize,
+            FifoCount::Records(_) => {
+                return Err(Error::SensorError(SensorError::UnexpectedFifoCountFormat))
+            }
+        };
+
+        let len = count.min(buffer.len());
+        if len > 0 {
+            self.read_fifo(&mut buffer[..len])?;
+        }
+
+        Ok(len)
+    }
+
+    /// Read raw data from the FIFO, filling the provided buffer
+    ///
+    /// The caller is responsible for making sure that the FIFO contains at
+    /// least as many bytes as the buffer can hold; see
+    /// [`Icm42670::fifo_count`].
+    pub fn read_fifo(&mut self, buffer: &mut [u8]) -> Result<(), Error<E>> {
+        // Reading from `FIFO_DATA` does not auto-increment the register address, so
+        // the entire buffer can be filled in a single transaction.
+        self.read_regs(&Bank0::FIFO_DATA, buffer)?;
+        self.fifo_data_read();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,350 @@
+use micromath::vector::I16x3;
+
+use crate::config::SensorDataEndian;
+
+/// FIFO is empty
+const HEADER_MSG: u8 = 0b1000_0000;
+/// Packet contains accelerometer data
+const HEADER_ACCEL: u8 = 0b0100_0000;
+/// Packet contains gyroscope data
+const HEADER_GYRO: u8 = 0b0010_0000;
+/// Packet is 20 bytes long, containing high resolution data
+const HEADER_20: u8 = 0b0001_0000;
+/// Bits indicating whether the packet contains a timestamp or FSYNC time
+const HEADER_TMST_FSYNC_MASK: u8 = 0b0000_1100;
+/// Packet contains a timestamp at the output data rate
+const HEADER_ODR_TIMESTAMP: u8 = 0b0000_1000;
+/// Packet contains FSYNC time, being the first sample following an FSYNC pulse
+const HEADER_FSYNC_TIME: u8 = 0b0000_1100;
+
+/// A single packet of sensor data decoded from the FIFO
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Packet {
+    /// Raw accelerometer data, if present in the packet
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub accel: Option<I16x3>,
+    /// Raw gyroscope data, if present in the packet
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    pub gyro: Option<I16x3>,
+    /// Raw temperature data, using the same scale as
+    /// [`Icm42670::temperature_raw`](crate::Icm42670::temperature_raw)
+    pub temperature: i16,
+    /// Timestamp or FSYNC time, if present in the packet
+    pub timestamp: Option<u16>,
+    /// Is this the first sample following an FSYNC pulse?
+    pub fsync: bool,
+}
+
+impl Packet {
+    /// Return the temperature in degrees centigrade
+    pub fn temperature(&self) -> f32 {
+        (self.temperature as f32 / 128.0) + 25.0
+    }
+}
+
+impl<'a> From<Frame<'a>> for Packet {
+    fn from(frame: Frame<'a>) -> Self {
+        frame.packet()
+    }
+}
+
+/// A borrowed view of a single packet within a buffer of FIFO data
+///
+/// Fields are decoded on access, directly from the underlying buffer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Frame<'a> {
+    bytes: &'a [u8],
+    endian: SensorDataEndian,
+}
+
+impl<'a> Frame<'a> {
+    /// Create a view of a complete packet, including its header
+    ///
+    /// The length of `bytes` must match the length determined by the packet's
+    /// header, and `endian` the byte order configured when it was written.
+    pub(crate) fn new(bytes: &'a [u8], endian: SensorDataEndian) -> Self {
+        Self { bytes, endian }
+    }
+
+    /// Determine the total length of a packet, in bytes, from its header
+    ///
+    /// Returns `None` if the header indicates that the FIFO is empty, or that
+    /// the packet contains no sensor data.
+    pub(crate) fn len(header: u8) -> Option<usize> {
+        if header & HEADER_MSG != 0 {
+            None
+        } else if header & HEADER_20 != 0 {
+            Some(20)
+        } else {
+            match (header & HEADER_ACCEL != 0, header & HEADER_GYRO != 0) {
+                (true, true) => Some(16),
+                (true, false) | (false, true) => Some(8),
+                (false, false) => None,
+            }
+        }
+    }
+
+    /// The raw bytes making up the packet, including its header
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// The packet's header byte
+    pub fn header(&self) -> u8 {
+        self.bytes[0]
+    }
+
+    /// Raw accelerometer data, if present in the packet
+    pub fn accel(&self) -> Option<I16x3> {
+        // Accelerometer data always immediately follows the header.
+        if self.header() & HEADER_ACCEL != 0 {
+            Some(self.vector(1))
+        } else {
+            None
+        }
+    }
+
+    /// Raw gyroscope data, if present in the packet
+    pub fn gyro(&self) -> Option<I16x3> {
+        // In 8-byte packets gyroscope data immediately follows the header, otherwise
+        // it follows the accelerometer data.
+        if self.header() & HEADER_GYRO == 0 {
+            None
+        } else if self.bytes.len() == 8 {
+            Some(self.vector(1))
+        } else {
+            Some(self.vector(7))
+        }
+    }
+
+    /// Raw temperature data, using the same scale as
+    /// [`Icm42670::temperature_raw`](crate::Icm42670::temperature_raw)
+    ///
+    /// Only 20-byte packets contain the full 16 bits of temperature data;
+    /// other packets contain a single byte, which has a coarser resolution.
+    pub fn temperature(&self) -> i16 {
+        match self.bytes.len() {
+            8 => (self.bytes[7] as i8 as i16) * 64,
+            16 => (self.bytes[13] as i8 as i16) * 64,
+            _ => self.word(13),
+        }
+    }
+
+    /// Timestamp or FSYNC time, if present in the packet
+    pub fn timestamp(&self) -> Option<u16> {
+        let idx = match self.bytes.len() {
+            16 => 14,
+            20 => 15,
+            _ => return None,
+        };
+
+        match self.header() & HEADER_TMST_FSYNC_MASK {
+            HEADER_ODR_TIMESTAMP | HEADER_FSYNC_TIME => Some(self.word(idx) as u16),
+            _ => None,
+        }
+    }
+
+    /// Is this the first sample following an FSYNC pulse?
+    ///
+    /// When set, the packet's [`timestamp`](Self::timestamp) holds the FSYNC
+    /// time rather than a timestamp.
+    pub fn fsync(&self) -> bool {
+        self.header() & HEADER_TMST_FSYNC_MASK == HEADER_FSYNC_TIME
+    }
+
+    /// Decode all of the packet's fields
+    ///
+    /// Any additional resolution present in 20-byte packets is discarded.
+    pub fn packet(&self) -> Packet {
+        Packet {
+            accel: self.accel(),
+            gyro: self.gyro(),
+            temperature: self.temperature(),
+            timestamp: self.timestamp(),
+            fsync: self.fsync(),
+        }
+    }
+
+    fn word(&self, idx: usize) -> i16 {
+        self.endian.word([self.bytes[idx], self.bytes[idx + 1]])
+    }
+
+    fn vector(&self, idx: usize) -> I16x3 {
+        I16x3::new(self.word(idx), self.word(idx + 2), self.word(idx + 4))
+    }
+}
+
+/// Iterator over the packets held in a buffer of FIFO data
+///
+/// This allows data which has already been read from the FIFO, for example
+/// via [`Icm42670::read_fifo_bytes`](crate::Icm42670::read_fifo_bytes) or by
+/// DMA, to be decoded in place without copying. Iteration stops at the first
+/// header indicating that the FIFO was empty, or at the first incomplete
+/// packet.
+#[derive(Clone, Debug)]
+pub struct FifoFrames<'a> {
+    bytes: &'a [u8],
+    endian: SensorDataEndian,
+}
+
+impl<'a> FifoFrames<'a> {
+    /// Create an iterator over the packets held in the provided buffer
+    ///
+    /// The sensor data byte order also applies to the FIFO, so `endian` must
+    /// be that configured when the data was written; see
+    /// [`Icm42670::sensor_data_endian`](crate::Icm42670::sensor_data_endian).
+    pub fn new(bytes: &'a [u8], endian: SensorDataEndian) -> Self {
+        Self { bytes, endian }
+    }
+
+    /// Any bytes remaining in the buffer which have not been yielded as part of
+    /// a complete packet
+    pub fn remainder(&self) -> &'a [u8] {
+        self.bytes
+    }
+}
+
+impl<'a> Iterator for FifoFrames<'a> {
+    type Item = Frame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = Frame::len(*self.bytes.first()?)?;
+        if self.bytes.len() < len {
+            return None;
+        }
+
+        let (frame, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Some(Frame::new(frame, self.endian))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a 16-byte packet holding both accelerometer and gyroscope data,
+    /// using big-endian sensor data
+    fn packet16(header: u8) -> [u8; 16] {
+        [
+            header, 0x00, 0x01, 0xFF, 0xFE, 0x00, 0x03, 0x00, 0x04, 0xFF, 0xFB, 0x00, 0x06, 0x02,
+            0x12, 0x34,
+        ]
+    }
+
+    #[test]
+    fn len_from_header() {
+        assert_eq!(Frame::len(0x80), None);
+        assert_eq!(Frame::len(0x00), None);
+        assert_eq!(Frame::len(0x40), Some(8));
+        assert_eq!(Frame::len(0x20), Some(8));
+        assert_eq!(Frame::len(0x60), Some(16));
+        assert_eq!(Frame::len(0x78), Some(20));
+    }
+
+    #[test]
+    fn decode_8_byte_accel_packet() {
+        let bytes = [0x40, 0x00, 0x01, 0xFF, 0xFE, 0x00, 0x03, 0x02];
+        let frame = Frame::new(&bytes, SensorDataEndian::Big);
+
+        assert_eq!(frame.accel(), Some(I16x3::new(1, -2, 3)));
+        assert_eq!(frame.gyro(), None);
+        assert_eq!(frame.temperature(), 128);
+        assert_eq!(frame.timestamp(), None);
+        assert!(!frame.fsync());
+    }
+
+    #[test]
+    fn decode_8_byte_gyro_packet() {
+        let bytes = [0x20, 0x00, 0x01, 0xFF, 0xFE, 0x00, 0x03, 0xFE];
+        let frame = Frame::new(&bytes, SensorDataEndian::Big);
+
+        assert_eq!(frame.accel(), None);
+        assert_eq!(frame.gyro(), Some(I16x3::new(1, -2, 3)));
+        assert_eq!(frame.temperature(), -128);
+    }
+
+    #[test]
+    fn decode_16_byte_packet() {
+        let bytes = packet16(0x68);
+        let packet = Frame::new(&bytes, SensorDataEndian::Big).packet();
+
+        assert_eq!(packet.accel, Some(I16x3::new(1, -2, 3)));
+        assert_eq!(packet.gyro, Some(I16x3::new(4, -5, 6)));
+        assert_eq!(packet.temperature, 128);
+        assert_eq!(packet.timestamp, Some(0x1234));
+        assert!(!packet.fsync);
+    }
+
+    #[test]
+    fn decode_16_byte_packet_little_endian() {
+        let bytes = [
+            0x60, 0x01, 0x00, 0xFE, 0xFF, 0x03, 0x00, 0x04, 0x00, 0xFB, 0xFF, 0x06, 0x00, 0x02,
+            0x34, 0x12,
+        ];
+        let frame = Frame::new(&bytes, SensorDataEndian::Little);
+
+        assert_eq!(frame.accel(), Some(I16x3::new(1, -2, 3)));
+        assert_eq!(frame.gyro(), Some(I16x3::new(4, -5, 6)));
+    }
+
+    #[test]
+    fn timestamp_header_patterns() {
+        let none = packet16(0x60);
+        let reserved = packet16(0x64);
+        let odr = packet16(0x68);
+        let fsync = packet16(0x6C);
+
+        let frame = |bytes| Frame::new(bytes, SensorDataEndian::Big);
+
+        assert_eq!(frame(&none).timestamp(), None);
+        assert_eq!(frame(&reserved).timestamp(), None);
+        assert_eq!(frame(&odr).timestamp(), Some(0x1234));
+        assert_eq!(frame(&fsync).timestamp(), Some(0x1234));
+
+        assert!(!frame(&reserved).fsync());
+        assert!(!frame(&odr).fsync());
+        assert!(frame(&fsync).fsync());
+    }
+
+    #[test]
+    fn decode_20_byte_packet() {
+        let bytes = [
+            0x78, 0x00, 0x01, 0xFF, 0xFE, 0x00, 0x03, 0x00, 0x04, 0xFF, 0xFB, 0x00, 0x06, 0x01,
+            0x00, 0x12, 0x34, 0x00, 0x00, 0x00,
+        ];
+        let packet = Frame::new(&bytes, SensorDataEndian::Big).packet();
+
+        assert_eq!(packet.accel, Some(I16x3::new(1, -2, 3)));
+        assert_eq!(packet.gyro, Some(I16x3::new(4, -5, 6)));
+        assert_eq!(packet.temperature, 256);
+        assert_eq!(packet.timestamp, Some(0x1234));
+    }
+
+    #[test]
+    fn frames_stop_at_truncated_packet() {
+        let mut bytes = [0u8; 24];
+        bytes[..16].copy_from_slice(&packet16(0x68));
+        bytes[16..].copy_from_slice(&packet16(0x68)[..8]);
+
+        let mut frames = FifoFrames::new(&bytes, SensorDataEndian::Big);
+
+        assert_eq!(frames.next().map(|frame| frame.as_bytes().len()), Some(16));
+        assert_eq!(frames.next(), None);
+        assert_eq!(frames.remainder(), &bytes[16..]);
+    }
+
+    #[test]
+    fn frames_stop_at_empty_header() {
+        let bytes = [0x40, 0x00, 0x01, 0xFF, 0xFE, 0x00, 0x03, 0x02, 0x80, 0x00];
+
+        let mut frames = FifoFrames::new(&bytes, SensorDataEndian::Big);
+
+        assert!(frames.next().is_some());
+        assert_eq!(frames.next(), None);
+        assert_eq!(frames.remainder(), &bytes[8..]);
+    }
+}
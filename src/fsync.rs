@@ -0,0 +1,221 @@
+//! Frame synchronization (FSYNC)
+//!
+//! An external signal, such as the frame clock of a camera, may be applied to
+//! pin 9. The first sample following each FSYNC pulse is flagged, allowing
+//! samples to be matched with the frames which triggered them.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    config::{Bitfield, Pin9Function, SensorDataEndian},
+    error::SensorError,
+    mode::{MregAccessible, PowerState},
+    register::{field, Bank0, Mreg1, RegisterBank},
+    Error,
+    Icm42670,
+    Interface,
+};
+
+/// Data word whose least significant bit carries the FSYNC flag
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FsyncTag {
+    /// The FSYNC flag is not reported in the sensor data registers
+    Disabled    = 0b000,
+    /// Temperature data
+    Temperature = 0b001,
+    /// Gyroscope X axis data
+    GyroX       = 0b010,
+    /// Gyroscope Y axis data
+    GyroY       = 0b011,
+    /// Gyroscope Z axis data
+    GyroZ       = 0b100,
+    /// Accelerometer X axis data
+    AccelX      = 0b101,
+    /// Accelerometer Y axis data
+    AccelY      = 0b110,
+    /// Accelerometer Z axis data
+    AccelZ      = 0b111,
+}
+
+impl FsyncTag {
+    /// The register holding the least significant byte of the tagged word
+    ///
+    /// The `*0` registers hold the least significant byte when data is
+    /// reported in big endian format, and the `*1` registers otherwise.
+    fn register(self, endian: SensorDataEndian) -> Option<Bank0> {
+        use FsyncTag::*;
+
+        let (lsb, msb) = match self {
+            Disabled => return None,
+            Temperature => (Bank0::TEMP_DATA0, Bank0::TEMP_DATA1),
+            GyroX => (Bank0::GYRO_DATA_X0, Bank0::GYRO_DATA_X1),
+            GyroY => (Bank0::GYRO_DATA_Y0, Bank0::GYRO_DATA_Y1),
+            GyroZ => (Bank0::GYRO_DATA_Z0, Bank0::GYRO_DATA_Z1),
+            AccelX => (Bank0::ACCEL_DATA_X0, Bank0::ACCEL_DATA_X1),
+            AccelY => (Bank0::ACCEL_DATA_Y0, Bank0::ACCEL_DATA_Y1),
+            AccelZ => (Bank0::ACCEL_DATA_Z0, Bank0::ACCEL_DATA_Z1),
+        };
+
+        match endian {
+            SensorDataEndian::Big => Some(lsb),
+            SensorDataEndian::Little => Some(msb),
+        }
+    }
+}
+
+impl Bitfield for FsyncTag {
+    const BITMASK: u8 = field::FSYNC_UI_SEL.mask();
+
+    fn bits(self) -> u8 {
+        field::FSYNC_UI_SEL.bits(self as u8)
+    }
+}
+
+impl Default for FsyncTag {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+impl TryFrom<u8> for FsyncTag {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use FsyncTag::*;
+
+        match value {
+            0b000 => Ok(Disabled),
+            0b001 => Ok(Temperature),
+            0b010 => Ok(GyroX),
+            0b011 => Ok(GyroY),
+            0b100 => Ok(GyroZ),
+            0b101 => Ok(AccelX),
+            0b110 => Ok(AccelY),
+            0b111 => Ok(AccelZ),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Edge of the FSYNC signal from which the FSYNC time is measured
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FsyncEdge {
+    /// Rising edge
+    Rising  = 0,
+    /// Falling edge
+    Falling = 1,
+}
+
+impl Bitfield for FsyncEdge {
+    const BITMASK: u8 = field::FSYNC_POLARITY.mask();
+
+    fn bits(self) -> u8 {
+        field::FSYNC_POLARITY.bits(self as u8)
+    }
+}
+
+impl Default for FsyncEdge {
+    fn default() -> Self {
+        Self::Rising
+    }
+}
+
+impl TryFrom<u8> for FsyncEdge {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use FsyncEdge::*;
+
+        match value {
+            0 => Ok(Rising),
+            1 => Ok(Falling),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// FSYNC configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FsyncConfig {
+    /// Data word whose least significant bit carries the FSYNC flag
+    pub tag: FsyncTag,
+    /// Edge of the FSYNC signal from which the FSYNC time is measured
+    pub edge: FsyncEdge,
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Read the current FSYNC configuration
+    pub fn fsync_config<D>(&mut self, delay: &mut D) -> Result<FsyncConfig, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let bits = self.read_mreg(delay, RegisterBank::MReg1, &Mreg1::FSYNC_CONFIG)?;
+
+        Ok(FsyncConfig {
+            tag: FsyncTag::try_from(field::FSYNC_UI_SEL.get(bits))?,
+            edge: FsyncEdge::try_from(field::FSYNC_POLARITY.get(bits))?,
+        })
+    }
+
+    /// Enable FSYNC on pin 9
+    ///
+    /// This assigns [`Pin9Function::Fsync`] to pin 9, so `INT2` and `CLKIN` are
+    /// unavailable while FSYNC is in use. The FSYNC flag is reported in the
+    /// FIFO regardless of the configured [`FsyncTag`]; see
+    /// [`Frame::fsync`](crate::Frame::fsync).
+    pub fn enable_fsync<D>(&mut self, delay: &mut D, config: FsyncConfig) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::FSYNC_CONFIG,
+            config.tag.bits() | config.edge.bits(),
+            FsyncTag::BITMASK | FsyncEdge::BITMASK,
+        )?;
+        self.set_pin9_function(delay, Pin9Function::Fsync)
+    }
+
+    /// Disable FSYNC, assigning [`Pin9Function::Int2`] to pin 9
+    pub fn disable_fsync<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::FSYNC_CONFIG,
+            FsyncTag::Disabled.bits(),
+            FsyncTag::BITMASK,
+        )?;
+        self.set_pin9_function(delay, Pin9Function::Int2)
+    }
+
+    /// Read the FSYNC flag from the least significant bit of the tagged data
+    /// word
+    ///
+    /// The flag is set in the first sample following an FSYNC pulse, and
+    /// replaces the least significant bit of the tagged word until the next
+    /// sample; it should therefore be read before new data becomes available.
+    /// Always returns `false` for [`FsyncTag::Disabled`].
+    pub fn fsync_flag(&mut self, tag: FsyncTag) -> Result<bool, Error<E>> {
+        match tag.register(self.data_endian) {
+            Some(reg) => Ok(self.read_reg(&reg)? & 0b1 != 0),
+            None => Ok(false),
+        }
+    }
+}
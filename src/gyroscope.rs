@@ -0,0 +1,65 @@
+//! Gyroscope traits
+//!
+//! These mirror the `Accelerometer` and `RawAccelerometer` traits of the
+//! `accelerometer` crate, allowing generic code to accept the gyroscope of any
+//! device in the same way.
+
+use core::fmt::Debug;
+
+use micromath::vector::{F32x3, I16x3};
+
+use crate::{mode::GyroEnabled, Error, Icm42670, Interface};
+
+/// Gyroscope which reports normalized angular rates
+pub trait Gyroscope {
+    /// Error type
+    type Error: Debug;
+
+    /// Get normalized angular rates for each of the three axes, in degrees per
+    /// second
+    fn gyro_norm(&mut self) -> Result<F32x3, Self::Error>;
+
+    /// Get the sample rate of the gyroscope data, in Hz
+    fn gyro_sample_rate(&mut self) -> Result<f32, Self::Error>;
+}
+
+/// Gyroscope which reports raw angular rates of type `V`
+pub trait RawGyroscope<V> {
+    /// Error type
+    type Error: Debug;
+
+    /// Get raw angular rates for each of the three axes
+    fn gyro_raw(&mut self) -> Result<V, Self::Error>;
+}
+
+impl<DI, E, MODE> Gyroscope for Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: GyroEnabled,
+{
+    type Error = Error<E>;
+
+    fn gyro_norm(&mut self) -> Result<F32x3, Self::Error> {
+        Icm42670::gyro_norm(self)
+    }
+
+    fn gyro_sample_rate(&mut self) -> Result<f32, Self::Error> {
+        let odr = self.gyro_odr()?;
+
+        Ok(odr.as_f32())
+    }
+}
+
+impl<DI, E, MODE> RawGyroscope<I16x3> for Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: GyroEnabled,
+{
+    type Error = Error<E>;
+
+    fn gyro_raw(&mut self) -> Result<I16x3, Self::Error> {
+        Icm42670::gyro_raw(self)
+    }
+}
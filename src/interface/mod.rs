@@ -0,0 +1,46 @@
+//! Host interfaces over which the device can be communicated with
+//!
+//! The driver itself is independent of the transport being used; all register
+//! accesses are performed via the [`Interface`] trait. Support for additional
+//! transports can be added by implementing this trait.
+
+pub(crate) use self::spi::SPI_READ;
+pub use self::{
+    i2c::{I2cInterface, I2cRefInterface},
+    spi::{SpiError, SpiInterface},
+};
+
+mod i2c;
+mod spi;
+
+/// A host interface over which the device's registers can be accessed
+pub trait Interface {
+    /// Error type of the underlying transport
+    type Error;
+
+    /// Lower bound on the time taken by any single register access, in
+    /// microseconds
+    ///
+    /// Successive accesses to the MREG banks must be separated by 10µs, of
+    /// which the driver only waits for the portion not already guaranteed to
+    /// have elapsed on the bus. The default of zero means the full 10µs is
+    /// always waited.
+    const MIN_ACCESS_US: u8 = 0;
+
+    /// Read consecutive registers starting at the provided address
+    ///
+    /// The device auto-increments the register address after each byte, so
+    /// the length of `buffer` determines how many registers are read.
+    fn read_regs(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Write a value to the register at the provided address
+    fn write_reg(&mut self, addr: u8, value: u8) -> Result<(), Self::Error>;
+
+    /// Read the register at the provided address
+    fn read_reg(&mut self, addr: u8) -> Result<u8, Self::Error> {
+        let mut buffer = [0u8];
+        self.read_regs(addr, &mut buffer)?;
+
+        Ok(buffer[0])
+    }
+}
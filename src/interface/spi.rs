@@ -0,0 +1,108 @@
+use core::fmt::{self, Debug, Display};
+
+use embedded_hal::{
+    blocking::spi::{Transfer, Write},
+    digital::v2::OutputPin,
+};
+
+use super::Interface;
+
+/// Set in the first byte of an SPI transaction to indicate a register read
+pub(crate) const SPI_READ: u8 = 0x80;
+
+/// Any type of error which may occur while communicating over SPI
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpiError<SPI, CS> {
+    /// Some error originating from the SPI peripheral
+    Spi(SPI),
+    /// Some error originating from the chip select pin
+    ChipSelect(CS),
+}
+
+impl<SPI, CS> Display for SpiError<SPI, CS>
+where
+    SPI: Debug,
+    CS: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpiError::Spi(e) => write!(f, "SPI error: {:?}", e),
+            SpiError::ChipSelect(e) => write!(f, "chip select error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl<SPI, CS> crate::error::StdError for SpiError<SPI, CS>
+where
+    SPI: Debug,
+    CS: Debug,
+{
+}
+
+/// SPI host interface
+///
+/// The device supports SPI modes 0 and 3, with clock speeds of up to 24 MHz.
+/// The chip select pin is driven low for the duration of each transaction.
+#[derive(Debug, Clone, Copy)]
+pub struct SpiInterface<SPI, CS> {
+    /// Underlying SPI peripheral
+    spi: SPI,
+    /// Active-low chip select pin
+    cs: CS,
+}
+
+impl<SPI, CS> SpiInterface<SPI, CS> {
+    /// Create a new SPI interface using the given chip select pin
+    pub fn new(spi: SPI, cs: CS) -> Self {
+        Self { spi, cs }
+    }
+
+    /// Return the underlying `SPI` instance and chip select pin
+    pub fn release(self) -> (SPI, CS) {
+        (self.spi, self.cs)
+    }
+}
+
+impl<SPI, CS, SE, PE> SpiInterface<SPI, CS>
+where
+    SPI: Transfer<u8, Error = SE> + Write<u8, Error = SE>,
+    CS: OutputPin<Error = PE>,
+{
+    /// Perform a transaction with the chip select pin asserted, making sure to
+    /// de-assert it again even if the transaction itself failed.
+    fn transaction<T>(
+        &mut self,
+        f: impl FnOnce(&mut SPI) -> Result<T, SE>,
+    ) -> Result<T, SpiError<SE, PE>> {
+        self.cs.set_low().map_err(SpiError::ChipSelect)?;
+        let result = f(&mut self.spi).map_err(SpiError::Spi);
+        self.cs.set_high().map_err(SpiError::ChipSelect)?;
+
+        result
+    }
+}
+
+impl<SPI, CS, SE, PE> Interface for SpiInterface<SPI, CS>
+where
+    SPI: Transfer<u8, Error = SE> + Write<u8, Error = SE>,
+    CS: OutputPin<Error = PE>,
+{
+    type Error = SpiError<SE, PE>;
+
+    fn read_regs(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        // The most significant bit of the first byte selects between a read (1) and
+        // a write (0) operation, with the remaining bits holding the address.
+        self.transaction(|spi| {
+            spi.write(&[addr | SPI_READ])?;
+            spi.transfer(buffer)?;
+
+            Ok(())
+        })
+    }
+
+    fn write_reg(&mut self, addr: u8, value: u8) -> Result<(), Self::Error> {
+        self.transaction(|spi| spi.write(&[addr & !SPI_READ, value]))
+    }
+}
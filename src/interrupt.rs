@@ -0,0 +1,688 @@
+//! Interrupt configuration, routing and status decoding
+
+use core::fmt::{self, Debug, Display};
+
+use embedded_hal::{blocking::delay::DelayUs, digital::v2::InputPin};
+
+use crate::{
+    mode::{MregAccessible, PowerState},
+    register::{field, Bank0, Field, Mreg1, RegisterBank},
+    Error,
+    Icm42670,
+    Interface,
+    InterruptClear,
+    InterruptDrive,
+    InterruptMode,
+    InterruptPin,
+    InterruptPolarity,
+    WomEvent,
+};
+
+/// Any type of error which may occur while waiting on an interrupt pin
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InterruptError<E, PIN> {
+    /// Some error resulting from interacting with the device
+    Sensor(Error<E>),
+    /// Some error originating from the interrupt input pin
+    Pin(PIN),
+}
+
+impl<E, PIN> From<Error<E>> for InterruptError<E, PIN> {
+    fn from(err: Error<E>) -> Self {
+        InterruptError::Sensor(err)
+    }
+}
+
+impl<E, PIN> Display for InterruptError<E, PIN>
+where
+    E: Debug,
+    PIN: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterruptError::Sensor(e) => Display::fmt(e, f),
+            InterruptError::Pin(e) => write!(f, "interrupt pin error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl<E, PIN> crate::error::StdError for InterruptError<E, PIN>
+where
+    E: Debug,
+    PIN: Debug,
+{
+}
+
+/// Host input pin connected to one of the device's interrupt pins
+///
+/// The waiting methods busy-wait on the input pin until the interrupt is
+/// asserted, then read the device's interrupt status to determine its cause.
+/// Configuring the device's interrupt pin in [`InterruptMode::Latched`] mode
+/// is recommended, as a short pulse may be missed while polling.
+#[derive(Debug, Clone, Copy)]
+pub struct InterruptInput<PIN> {
+    /// Underlying input pin
+    pin: PIN,
+    /// Logic level of the pin when the interrupt is asserted
+    polarity: InterruptPolarity,
+}
+
+impl<PIN> InterruptInput<PIN>
+where
+    PIN: InputPin,
+{
+    /// Create a new interrupt input using the given pin, whose polarity must
+    /// match that configured for the device's interrupt pin
+    pub fn new(pin: PIN, polarity: InterruptPolarity) -> Self {
+        Self { pin, polarity }
+    }
+
+    /// Return the underlying input pin
+    pub fn release(self) -> PIN {
+        self.pin
+    }
+
+    /// Is the interrupt currently asserted?
+    pub fn is_asserted(&self) -> Result<bool, PIN::Error> {
+        match self.polarity {
+            InterruptPolarity::ActiveLow => self.pin.is_low(),
+            InterruptPolarity::ActiveHigh => self.pin.is_high(),
+        }
+    }
+
+    /// Wait until new sensor data is available
+    ///
+    /// The data ready status is checked before waiting on the pin, so that a
+    /// sample which arrived before this was called is not missed, as would
+    /// otherwise be the case in [`InterruptMode::Pulsed`] mode. Only the data
+    /// ready status is read, so any other interrupts which are routed to the
+    /// same pin are left pending.
+    pub fn wait_for_data_ready<DI, E, MODE>(
+        &mut self,
+        imu: &mut Icm42670<DI, MODE>,
+    ) -> Result<(), InterruptError<E, PIN::Error>>
+    where
+        DI: Interface<Error = E>,
+        E: Debug,
+        MODE: PowerState,
+    {
+        while !imu.data_ready()? {
+            self.wait_until_asserted()?;
+        }
+
+        Ok(())
+    }
+
+    /// Wait until any interrupt is asserted, returning the decoded interrupt
+    /// status
+    ///
+    /// If the driver holds flags which were cleared on the device but not yet
+    /// reported, for example by [`Icm42670::fifo_full`], they are returned
+    /// immediately without waiting on the pin, whose assertion has already
+    /// been consumed.
+    pub fn wait_for_event<DI, E, MODE>(
+        &mut self,
+        imu: &mut Icm42670<DI, MODE>,
+    ) -> Result<InterruptStatus, InterruptError<E, PIN::Error>>
+    where
+        DI: Interface<Error = E>,
+        E: Debug,
+        MODE: PowerState,
+    {
+        if !imu.interrupts_pending() {
+            self.wait_until_asserted()?;
+        }
+
+        Ok(imu.interrupt_status()?)
+    }
+
+    // -----------------------------------------------------------------------
+    // PRIVATE
+
+    fn wait_until_asserted<E>(&self) -> Result<(), InterruptError<E, PIN::Error>> {
+        while !self.is_asserted().map_err(InterruptError::Pin)? {}
+
+        Ok(())
+    }
+}
+
+/// Electrical configuration of an interrupt pin
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IntPinConfig {
+    /// Whether the pin is pulsed or latched when asserted
+    pub mode: InterruptMode,
+    /// Output drive circuit of the pin
+    pub drive: InterruptDrive,
+    /// Output polarity of the pin
+    pub polarity: InterruptPolarity,
+}
+
+impl IntPinConfig {
+    /// Bit value of the configuration for `INT1`, which is positioned for
+    /// either pin using `INT1_CONFIG` or `INT2_CONFIG`
+    pub(crate) fn bits(self) -> u8 {
+        field::INT1_MODE.bits(self.mode as u8)
+            | field::INT1_DRIVE_CIRCUIT.bits(self.drive as u8)
+            | field::INT1_POLARITY.bits(self.polarity as u8)
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        // Each field is a single bit, so conversion cannot fail
+        Self {
+            mode: InterruptMode::try_from(field::INT1_MODE.get(bits)).unwrap(),
+            drive: InterruptDrive::try_from(field::INT1_DRIVE_CIRCUIT.get(bits)).unwrap(),
+            polarity: InterruptPolarity::try_from(field::INT1_POLARITY.get(bits)).unwrap(),
+        }
+    }
+}
+
+/// Conditions under which each of the data interrupts is cleared
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InterruptClearConfig {
+    /// Clear condition of the data ready interrupt
+    pub data_ready: InterruptClear,
+    /// Clear condition of the FIFO threshold interrupt
+    pub fifo_threshold: InterruptClear,
+    /// Clear condition of the FIFO full interrupt
+    pub fifo_full: InterruptClear,
+}
+
+impl InterruptClearConfig {
+    const BITMASK: u8 = field::UI_DRDY_INT_CLEAR.mask()
+        | field::FIFO_THS_INT_CLEAR.mask()
+        | field::FIFO_FULL_INT_CLEAR.mask();
+
+    fn bits(self) -> u8 {
+        field::UI_DRDY_INT_CLEAR.bits(self.data_ready as u8)
+            | field::FIFO_THS_INT_CLEAR.bits(self.fifo_threshold as u8)
+            | field::FIFO_FULL_INT_CLEAR.bits(self.fifo_full as u8)
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        // Every two-bit value is a valid discriminant, so conversion cannot fail
+        Self {
+            data_ready: InterruptClear::try_from(field::UI_DRDY_INT_CLEAR.get(bits)).unwrap(),
+            fifo_threshold: InterruptClear::try_from(field::FIFO_THS_INT_CLEAR.get(bits)).unwrap(),
+            fifo_full: InterruptClear::try_from(field::FIFO_FULL_INT_CLEAR.get(bits)).unwrap(),
+        }
+    }
+}
+
+/// Interrupt sources which can be routed to an interrupt pin
+///
+/// The APEX interrupts are routed separately, using
+/// [`Icm42670::enable_apex_interrupt`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum InterruptSource {
+    /// Self-test has completed
+    SelfTestDone,
+    /// An FSYNC event has occurred
+    Fsync,
+    /// The PLL is ready
+    PllReady,
+    /// A software reset has completed
+    ResetDone,
+    /// New sensor data is available
+    DataReady,
+    /// The FIFO watermark has been reached
+    FifoThreshold,
+    /// The FIFO is full
+    FifoFull,
+    /// The AGC is ready
+    AgcReady,
+    /// An I3C protocol error has occurred
+    I3cProtocolError,
+    /// Wake on Motion has detected motion on the X axis
+    WomX,
+    /// Wake on Motion has detected motion on the Y axis
+    WomY,
+    /// Wake on Motion has detected motion on the Z axis
+    WomZ,
+}
+
+impl InterruptSource {
+    /// Every interrupt source
+    pub(crate) const ALL: [InterruptSource; 12] = [
+        InterruptSource::SelfTestDone,
+        InterruptSource::Fsync,
+        InterruptSource::PllReady,
+        InterruptSource::ResetDone,
+        InterruptSource::DataReady,
+        InterruptSource::FifoThreshold,
+        InterruptSource::FifoFull,
+        InterruptSource::AgcReady,
+        InterruptSource::I3cProtocolError,
+        InterruptSource::WomX,
+        InterruptSource::WomY,
+        InterruptSource::WomZ,
+    ];
+
+    /// Field holding the source's enable bit for the given pin
+    pub(crate) fn enable_field(self, pin: InterruptPin) -> Field<Bank0> {
+        use InterruptSource::*;
+
+        let field = match self {
+            SelfTestDone => field::ST_INT1_EN,
+            Fsync => field::FSYNC_INT1_EN,
+            PllReady => field::PLL_RDY_INT1_EN,
+            ResetDone => field::RESET_DONE_INT1_EN,
+            DataReady => field::DRDY_INT1_EN,
+            FifoThreshold => field::FIFO_THS_INT1_EN,
+            FifoFull => field::FIFO_FULL_INT1_EN,
+            AgcReady => field::AGC_RDY_INT1_EN,
+            I3cProtocolError => field::I3C_PROTOCOL_ERROR_INT1_EN,
+            WomZ => field::WOM_Z_INT1_EN,
+            WomY => field::WOM_Y_INT1_EN,
+            WomX => field::WOM_X_INT1_EN,
+        };
+
+        // `INT_SOURCE3`/`INT_SOURCE4` hold the enable bits for `INT2` at the same
+        // positions as `INT_SOURCE0`/`INT_SOURCE1` do for `INT1`
+        match (pin, field.reg) {
+            (InterruptPin::Int1, _) => field,
+            (InterruptPin::Int2, Bank0::INT_SOURCE0) => Field {
+                reg: Bank0::INT_SOURCE3,
+                ..field
+            },
+            (InterruptPin::Int2, _) => Field {
+                reg: Bank0::INT_SOURCE4,
+                ..field
+            },
+        }
+    }
+}
+
+impl InterruptPin {
+    /// Field holding the electrical configuration of the pin
+    pub(crate) fn config_field(self) -> Field<Bank0> {
+        match self {
+            InterruptPin::Int1 => field::INT1_CONFIG,
+            InterruptPin::Int2 => field::INT2_CONFIG,
+        }
+    }
+}
+
+/// Decoded contents of the interrupt status registers
+///
+/// Each flag indicates whether the corresponding interrupt has been raised
+/// since the interrupt status was last read.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InterruptStatus {
+    /// New sensor data is available
+    pub data_ready: bool,
+    /// Self-test has completed
+    pub self_test_done: bool,
+    /// An FSYNC event has occurred
+    pub fsync: bool,
+    /// The PLL is ready
+    pub pll_ready: bool,
+    /// A software reset has completed
+    pub reset_done: bool,
+    /// The FIFO watermark has been reached
+    pub fifo_threshold: bool,
+    /// The FIFO is full
+    pub fifo_full: bool,
+    /// The AGC is ready
+    pub agc_ready: bool,
+    /// Significant motion has been detected
+    pub significant_motion: bool,
+    /// Axes on which Wake on Motion has detected motion
+    pub wake_on_motion: WomEvent,
+    /// A step has been detected by the pedometer
+    pub step_detected: bool,
+    /// The pedometer's step count has overflowed
+    pub step_count_overflow: bool,
+    /// A tilt has been detected
+    pub tilt: bool,
+    /// A freefall has been detected
+    pub freefall: bool,
+    /// Low-g has been detected
+    pub low_g: bool,
+}
+
+impl InterruptStatus {
+    /// Iterate over the events indicated by the interrupt status
+    pub fn events(self) -> SensorEvents {
+        SensorEvents {
+            status: self,
+            index: 0,
+        }
+    }
+
+    /// Decode the contents of `INT_STATUS_DRDY`, `INT_STATUS`, `INT_STATUS2`
+    /// and `INT_STATUS3`, in that order
+    pub(crate) fn from_bytes(bytes: [u8; 4]) -> Self {
+        let [drdy, status, status2, status3] = bytes;
+        let flag = |field: Field<Bank0>, byte: u8| field.get(byte) != 0;
+
+        Self {
+            data_ready: flag(field::DATA_RDY_INT, drdy),
+            self_test_done: flag(field::ST_INT, status),
+            fsync: flag(field::FSYNC_INT, status),
+            pll_ready: flag(field::PLL_RDY_INT, status),
+            reset_done: flag(field::RESET_DONE_INT, status),
+            fifo_threshold: flag(field::FIFO_THS_INT, status),
+            fifo_full: flag(field::FIFO_FULL_INT, status),
+            agc_ready: flag(field::AGC_RDY_INT, status),
+            significant_motion: flag(field::SMD_INT, status2),
+            wake_on_motion: WomEvent {
+                x: flag(field::WOM_X_INT, status2),
+                y: flag(field::WOM_Y_INT, status2),
+                z: flag(field::WOM_Z_INT, status2),
+            },
+            step_detected: flag(field::STEP_DET_INT, status3),
+            step_count_overflow: flag(field::STEP_CNT_OVF_INT, status3),
+            tilt: flag(field::TILT_DET_INT, status3),
+            freefall: flag(field::FF_DET_INT, status3),
+            low_g: flag(field::LOWG_DET_INT, status3),
+        }
+    }
+}
+
+/// Events indicated by the interrupt status registers
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SensorEvent {
+    /// New sensor data is available
+    DataReady,
+    /// Self-test has completed
+    SelfTestDone,
+    /// An FSYNC event has occurred
+    Fsync,
+    /// The PLL is ready
+    PllReady,
+    /// A software reset has completed
+    ResetDone,
+    /// The FIFO watermark has been reached
+    FifoWatermark,
+    /// The FIFO is full
+    FifoFull,
+    /// The AGC is ready
+    AgcReady,
+    /// Significant motion has been detected
+    SignificantMotion,
+    /// Wake on Motion has detected motion on at least one axis
+    WakeOnMotion(WomEvent),
+    /// A step has been detected by the pedometer
+    StepDetected,
+    /// The pedometer's step count has overflowed
+    StepCountOverflow,
+    /// A tilt has been detected
+    Tilt,
+    /// A freefall has been detected
+    Freefall,
+    /// Low-g has been detected
+    LowG,
+}
+
+/// Iterator over the events indicated by an [`InterruptStatus`]
+#[derive(Clone, Debug)]
+pub struct SensorEvents {
+    status: InterruptStatus,
+    index: usize,
+}
+
+impl Iterator for SensorEvents {
+    type Item = SensorEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use SensorEvent::*;
+
+        let s = &self.status;
+        let events = [
+            (s.data_ready, DataReady),
+            (s.self_test_done, SelfTestDone),
+            (s.fsync, Fsync),
+            (s.pll_ready, PllReady),
+            (s.reset_done, ResetDone),
+            (s.fifo_threshold, FifoWatermark),
+            (s.fifo_full, FifoFull),
+            (s.agc_ready, AgcReady),
+            (s.significant_motion, SignificantMotion),
+            (s.wake_on_motion.any(), WakeOnMotion(s.wake_on_motion)),
+            (s.step_detected, StepDetected),
+            (s.step_count_overflow, StepCountOverflow),
+            (s.tilt, Tilt),
+            (s.freefall, Freefall),
+            (s.low_g, LowG),
+        ];
+
+        while let Some(&(raised, event)) = events.get(self.index) {
+            self.index += 1;
+
+            if raised {
+                return Some(event);
+            }
+        }
+
+        None
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Read and decode all of the interrupt status registers
+    ///
+    /// The registers are read in a single burst, so that the flags raised by a
+    /// single event are not split across multiple reads. Any flags which were
+    /// cleared on the device by an earlier read, such as by
+    /// [`Icm42670::tilt_detected`], but not reported by it are included, so no
+    /// event is reported twice or lost.
+    ///
+    /// Reading a status register clears its flags on the device, other than
+    /// those of any data interrupts configured with
+    /// [`InterruptClear::DataRead`], so every flag in the returned status
+    /// should be handled by the caller.
+    pub fn interrupt_status(&mut self) -> Result<InterruptStatus, Error<E>> {
+        // `INT_STATUS_DRDY`, `INT_STATUS`, `INT_STATUS2` and `INT_STATUS3` occupy
+        // consecutive addresses
+        let mut buffer = [0u8; 4];
+        self.read_regs(&Bank0::INT_STATUS_DRDY, &mut buffer)?;
+
+        for (bits, pending) in buffer.iter_mut().zip(&mut self.pending_interrupts) {
+            *bits |= core::mem::take(pending);
+        }
+
+        Ok(InterruptStatus::from_bytes(buffer))
+    }
+
+    /// Read all of the interrupt status registers, returning an iterator over
+    /// the events which have occurred
+    ///
+    /// See [`Icm42670::interrupt_status`].
+    pub fn poll_events(&mut self) -> Result<SensorEvents, Error<E>> {
+        Ok(self.interrupt_status()?.events())
+    }
+
+    /// Has new sensor data become available since the data ready status was
+    /// last cleared?
+    ///
+    /// When the data ready interrupt is configured to clear on status read,
+    /// which is the default, subsequent calls will return `false` until the
+    /// next sample arrives. Otherwise the status remains set until the sensor
+    /// data registers are read. `INT_STATUS_DRDY` holds no other flags, so no
+    /// other event is consumed.
+    pub fn data_ready(&mut self) -> Result<bool, Error<E>> {
+        self.take_interrupt(field::DATA_RDY_INT)
+    }
+
+    /// Route the given interrupt source to an interrupt pin
+    ///
+    /// An interrupt source may be routed to both pins simultaneously.
+    pub fn enable_interrupt(
+        &mut self,
+        source: InterruptSource,
+        pin: InterruptPin,
+    ) -> Result<(), Error<E>> {
+        self.set_interrupt(source, pin, true)
+    }
+
+    /// Stop routing the given interrupt source to an interrupt pin
+    pub fn disable_interrupt(
+        &mut self,
+        source: InterruptSource,
+        pin: InterruptPin,
+    ) -> Result<(), Error<E>> {
+        self.set_interrupt(source, pin, false)
+    }
+
+    /// Is the given interrupt source routed to the interrupt pin?
+    pub fn interrupt_enabled(
+        &mut self,
+        source: InterruptSource,
+        pin: InterruptPin,
+    ) -> Result<bool, Error<E>> {
+        let enabled = self.read_field(source.enable_field(pin))? != 0;
+
+        Ok(enabled)
+    }
+
+    /// Read the conditions under which each of the data interrupts is cleared
+    pub fn interrupt_clear<D>(&mut self, delay: &mut D) -> Result<InterruptClearConfig, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let bits = self.read_mreg(delay, RegisterBank::MReg1, &Mreg1::INT_CONFIG0)?;
+        self.interrupt_clear =
+            InterruptClearConfig::from_bits(bits & InterruptClearConfig::BITMASK);
+
+        Ok(self.interrupt_clear)
+    }
+
+    /// Set the conditions under which each of the data interrupts is cleared
+    ///
+    /// Flags which are cleared on status read are retained by the driver until
+    /// reported, whereas those cleared only on data read remain set on the
+    /// device until the associated data is read.
+    pub fn set_interrupt_clear<D>(
+        &mut self,
+        delay: &mut D,
+        config: InterruptClearConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::INT_CONFIG0,
+            config.bits(),
+            InterruptClearConfig::BITMASK,
+        )?;
+        self.interrupt_clear = config;
+
+        Ok(())
+    }
+
+    /// Read the electrical configuration of the specified interrupt pin
+    pub fn interrupt_pin_config(&mut self, pin: InterruptPin) -> Result<IntPinConfig, Error<E>> {
+        let bits = self.read_field(pin.config_field())?;
+
+        Ok(IntPinConfig::from_bits(bits))
+    }
+
+    /// Set the electrical configuration of the specified interrupt pin
+    pub fn configure_interrupt_pin(
+        &mut self,
+        pin: InterruptPin,
+        config: IntPinConfig,
+    ) -> Result<(), Error<E>> {
+        self.write_field(pin.config_field(), config.bits())
+    }
+
+    /// Set the electrical configuration of pin `INT1`
+    pub fn config_int1(&mut self, config: IntPinConfig) -> Result<(), Error<E>> {
+        self.configure_interrupt_pin(InterruptPin::Int1, config)
+    }
+
+    /// Set the electrical configuration of pin `INT2`
+    ///
+    /// `INT2` shares pin 9 with `FSYNC` and `CLKIN`, and is only driven when
+    /// pin 9 is assigned [`Pin9Function::Int2`](crate::Pin9Function::Int2).
+    pub fn config_int2(&mut self, config: IntPinConfig) -> Result<(), Error<E>> {
+        self.configure_interrupt_pin(InterruptPin::Int2, config)
+    }
+
+    /// Report whether the interrupt flag held in the given field has been
+    /// raised, including if it was cleared on the device by an earlier read
+    /// but not yet reported
+    ///
+    /// Reading the status register clears every flag which it holds, so any
+    /// other flags which were raised are retained by the driver until they are
+    /// reported.
+    pub(crate) fn take_interrupt(&mut self, field: Field<Bank0>) -> Result<bool, Error<E>> {
+        Ok(self.take_interrupts(field.reg, field.mask())? != 0)
+    }
+
+    /// Report which of the interrupt flags in `mask` have been raised, as by
+    /// [`Icm42670::take_interrupt`]
+    pub(crate) fn take_interrupts(&mut self, reg: Bank0, mask: u8) -> Result<u8, Error<E>> {
+        let idx = reg as usize - Bank0::INT_STATUS_DRDY as usize;
+        let bits = self.read_reg(&reg)? | self.pending_interrupts[idx];
+        self.pending_interrupts[idx] = bits & !mask & self.cleared_on_status_read(reg);
+
+        Ok(bits & mask)
+    }
+
+    /// Are any interrupt flags held by the driver which have not yet been
+    /// reported?
+    pub(crate) fn interrupts_pending(&self) -> bool {
+        self.pending_interrupts.iter().any(|&bits| bits != 0)
+    }
+
+    /// Forget any retained FIFO interrupt flags which the device would have
+    /// cleared when data was read from the FIFO
+    #[cfg(feature = "fifo")]
+    pub(crate) fn fifo_data_read(&mut self) {
+        for (clear, field) in self.data_interrupts() {
+            if field.reg as u8 == Bank0::INT_STATUS as u8 && clear != InterruptClear::StatusRead {
+                self.pending_interrupts[1] &= !field.mask();
+            }
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // PRIVATE
+
+    /// Clear condition of each of the data interrupts, along with its flag
+    fn data_interrupts(&self) -> [(InterruptClear, Field<Bank0>); 3] {
+        [
+            (self.interrupt_clear.data_ready, field::DATA_RDY_INT),
+            (self.interrupt_clear.fifo_threshold, field::FIFO_THS_INT),
+            (self.interrupt_clear.fifo_full, field::FIFO_FULL_INT),
+        ]
+    }
+
+    /// Mask of the flags in the given status register which are cleared on the
+    /// device by reading it
+    fn cleared_on_status_read(&self, reg: Bank0) -> u8 {
+        self.data_interrupts()
+            .iter()
+            .filter(|(clear, field)| {
+                field.reg as u8 == reg as u8 && *clear == InterruptClear::DataRead
+            })
+            .fold(0xFF, |mask, (_, field)| mask & !field.mask())
+    }
+
+    fn set_interrupt(
+        &mut self,
+        source: InterruptSource,
+        pin: InterruptPin,
+        enabled: bool,
+    ) -> Result<(), Error<E>> {
+        self.write_field(source.enable_field(pin), enabled as u8)
+    }
+}
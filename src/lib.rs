@@ -2,8 +2,8 @@
 //!
 //! The ICM-42670 combines a 3-axis accelerometer with a 3-axis gyroscope into a
 //! single package. It has a configurable host interface which supports I²C,
-//! SPI, and I3C communications. Presently this driver only supports using the
-//! I²C interface.
+//! SPI, and I3C communications. Presently this driver supports using either
+//! the I²C or the SPI interface.
 //!
 //! For additional information about this device please refer to the
 //! [datasheet].
@@ -13,55 +13,486 @@
 
 #![no_std]
 
-use core::fmt::Debug;
+#[cfg(feature = "std")]
+extern crate std;
 
+use core::{fmt::Debug, marker::PhantomData};
+
+#[cfg(feature = "accelerometer")]
 pub use accelerometer;
-use accelerometer::{
-    error::Error as AccelerometerError,
-    vector::{F32x3, I16x3},
-    Accelerometer,
-    RawAccelerometer,
+#[cfg(feature = "accelerometer")]
+use accelerometer::{error::Error as AccelerometerError, Accelerometer, RawAccelerometer};
+use embedded_hal::{
+    blocking::{
+        delay::{DelayMs, DelayUs},
+        i2c::{Write, WriteRead},
+        spi::{Transfer, Write as SpiWrite},
+    },
+    digital::v2::OutputPin,
 };
-use embedded_hal::blocking::{
-    delay::DelayUs,
-    i2c::{Write, WriteRead},
+pub use micromath::vector;
+use micromath::vector::{F32x3, I16x3};
+pub use nb;
+
+#[cfg(feature = "apex")]
+pub use crate::apex::{
+    Activity,
+    ApexData,
+    ApexInterrupt,
+    DmpOdr,
+    FreefallConfig,
+    FreefallEvent,
+    HighGConfig,
+    LowGConfig,
+    PedometerConfig,
+    PedometerMode,
+    SmdConfig,
+    SmdSensitivity,
+    StepCounter,
+    TiltConfig,
+    TiltWaitTime,
+};
+#[cfg(feature = "embedded-storage")]
+pub use crate::storage::StorageError;
+pub use crate::{
+    calibration::{
+        Calibration,
+        GyroCalibrationTarget,
+        Orientation,
+        SensorCalibration,
+        SixPositionCalibration,
+    },
+    config::{
+        AccelLpf,
+        AccelOdr,
+        AccelRange,
+        Address,
+        ApexFeatures,
+        DeviceVariant,
+        GyroLpf,
+        GyroOdr,
+        GyroRange,
+        InterruptClear,
+        InterruptDrive,
+        InterruptMode,
+        InterruptPin,
+        InterruptPolarity,
+        Pin9Function,
+        PowerMode,
+        SensorDataEndian,
+    },
+    data::{Data, FastData, FixedData, RawData, Samples},
+    dma::BurstRead,
+    error::{Error, SensorError},
+    fsync::{FsyncConfig, FsyncEdge, FsyncTag},
+    gyroscope::{Gyroscope, RawGyroscope},
+    interface::{I2cInterface, I2cRefInterface, Interface, SpiError, SpiInterface},
+    interrupt::{
+        IntPinConfig,
+        InterruptClearConfig,
+        InterruptError,
+        InterruptInput,
+        InterruptSource,
+        InterruptStatus,
+        SensorEvent,
+        SensorEvents,
+    },
+    observer::{Bank, RegisterObserver},
+    selftest::{SelfTestAxes, SelfTestAxis, SelfTestLimits, SelfTestResult},
+    settings::{Config, InterruptRouting},
+    sync::{ClockMapping, ClockSync},
+    thermal::{GyroTempModel, TempBiasTable},
+    timestamp::{TimestampConfig, TimestampResolution, TimestampTracker},
+    wom::{WomConfig, WomEvent, WomInterruptMode, WomMode},
 };
-
 use crate::{
-    config::Bitfield,
-    error::SensorError,
-    register::{Bank0, Register, RegisterBank},
+    config::{Bitfield, CachedConfig},
+    mode::{AccelEnabled, Dynamic, GyroEnabled, MregAccessible, PowerState},
+    register::{field, Bank0, Field, Mreg1, Register, RegisterBank},
 };
+#[cfg(feature = "fifo")]
 pub use crate::{
-    config::{AccelOdr, AccelRange, Address, GyroOdr, GyroRange, PowerMode},
-    error::Error,
+    config::{FifoBypass, FifoCountEndian, FifoCountFormat, FifoMode},
+    fifo::{FifoConfig, FifoContent, FifoCount, FifoFrames, FifoPackets, Frame, Packet},
 };
 
+#[cfg(feature = "apex")]
+mod apex;
+mod calibration;
 mod config;
+mod data;
+mod diagnostics;
+mod dma;
 mod error;
+#[cfg(feature = "fifo")]
+mod fifo;
+mod fsync;
+mod gyroscope;
+mod interface;
+mod interrupt;
+pub mod mode;
+mod observer;
+mod offset;
+#[cfg(feature = "advanced")]
+mod raw;
 mod register;
+mod selftest;
+mod settings;
+#[cfg(feature = "embedded-storage")]
+mod storage;
+mod sync;
+mod thermal;
+mod timestamp;
+mod trace;
+mod wom;
 
 /// Re-export any traits which may be required by end users
 pub mod prelude {
+    #[cfg(feature = "accelerometer")]
     pub use accelerometer::{
         Accelerometer as _accelerometer_Accelerometer,
         RawAccelerometer as _accelerometer_RawAccelerometer,
     };
+
+    pub use crate::gyroscope::{
+        Gyroscope as _icm42670_Gyroscope,
+        RawGyroscope as _icm42670_RawGyroscope,
+    };
 }
 
 /// ICM-42670 driver
+///
+/// The power mode of the device may optionally be encoded in the type of the
+/// driver; see the [`mode`] module for more information.
 #[derive(Debug, Clone, Copy)]
-pub struct Icm42670<I2C> {
-    /// Underlying I²C peripheral
-    i2c: I2C,
-    /// I²C slave address to use
-    address: Address,
+pub struct Icm42670<DI, MODE = Dynamic> {
+    /// Underlying host interface
+    interface: DI,
+    /// APEX features which have been enabled
+    apex: ApexFeatures,
+    /// Byte order in which sensor data is reported
+    data_endian: SensorDataEndian,
+    /// Ranges and output data rates of the sensors, once known
+    cached: CachedConfig,
+    /// Interrupt status flags which were cleared on the device by reading
+    /// them, but which have not yet been reported, indexed as for
+    /// [`InterruptStatus::from_bytes`]
+    pending_interrupts: [u8; 4],
+    /// Conditions under which each of the data interrupts is cleared
+    interrupt_clear: InterruptClearConfig,
+    /// Calibration applied in software to normalized sensor data
+    calibration: Calibration,
+    /// Temperature compensation model applied to the gyroscope bias
+    gyro_temp_model: Option<GyroTempModel>,
+    /// Part detected on the bus during initialization
+    variant: DeviceVariant,
+    /// Observer notified of every register access
+    observer: Option<&'static dyn RegisterObserver>,
+    /// Configuration most recently applied using [`Icm42670::configure`] or
+    /// [`Icm42670::apply_config`]
+    config: Option<Config>,
+    /// Power mode of the device, if encoded in the type
+    mode: PhantomData<MODE>,
 }
 
-impl<I2C, E> Icm42670<I2C>
+impl<I2C, E> Icm42670<I2cInterface<I2C>>
 where
     I2C: Write<Error = E> + WriteRead<Error = E>,
     E: Debug,
+{
+    /// Instantiate a new instance of the driver over I²C and initialize the
+    /// device
+    pub fn new(i2c: I2C, address: Address) -> Result<Self, Error<E>> {
+        Self::with_interface(I2cInterface::new(i2c, address))
+    }
+
+    /// Instantiate a new instance of the driver over I²C and initialize the
+    /// device, without verifying the device ID
+    ///
+    /// See [`Icm42670::with_interface_unchecked`].
+    pub fn new_unchecked(i2c: I2C, address: Address) -> Result<Self, Error<E>> {
+        Self::with_interface_unchecked(I2cInterface::new(i2c, address))
+    }
+
+    /// Instantiate a new instance of the driver over I²C and initialize the
+    /// device, accepting additional device IDs
+    ///
+    /// See [`Icm42670::with_interface_accepting`].
+    pub fn new_accepting(
+        i2c: I2C,
+        address: Address,
+        device_ids: &[u8],
+    ) -> Result<(Self, u8), Error<E>> {
+        Self::with_interface_accepting(I2cInterface::new(i2c, address), device_ids)
+    }
+
+    /// Instantiate a new instance of the driver over I²C and initialize the
+    /// device in the provided power mode
+    ///
+    /// See [`Icm42670::with_interface_and_power_mode`].
+    pub fn new_with_power_mode(
+        i2c: I2C,
+        address: Address,
+        power_mode: Option<PowerMode>,
+    ) -> Result<Self, Error<E>> {
+        Self::with_interface_and_power_mode(I2cInterface::new(i2c, address), power_mode)
+    }
+
+    /// Instantiate a new instance of the driver over I²C and initialize the
+    /// device, immediately following power-on
+    ///
+    /// See [`Icm42670::with_interface_and_delay`].
+    pub fn new_with_delay<D>(
+        i2c: I2C,
+        address: Address,
+        delay: &mut D,
+        retries: u8,
+    ) -> Result<Self, Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        Self::with_interface_and_delay(I2cInterface::new(i2c, address), delay, retries)
+    }
+
+    /// Instantiate a new instance of the driver over I²C, leaving the
+    /// configuration of the device untouched
+    ///
+    /// See [`Icm42670::attach_interface`].
+    pub fn attach(i2c: I2C, address: Address) -> Result<Self, Error<E>> {
+        Self::attach_interface(I2cInterface::new(i2c, address))
+    }
+
+    /// Instantiate a new instance of the driver over I²C and bring the device
+    /// up in the provided configuration
+    ///
+    /// The configuration is validated using [`Config::validate`] before any
+    /// bus traffic occurs.
+    pub fn new_with_config<D>(
+        i2c: I2C,
+        address: Address,
+        delay: &mut D,
+        config: &Config,
+    ) -> Result<Self, Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        config.validate()?;
+
+        let mut me = Self::new(i2c, address)?;
+        me.configure(delay, config)?;
+
+        Ok(me)
+    }
+}
+
+impl<I2C, MODE> Icm42670<I2cInterface<I2C>, MODE> {
+    /// Return the raw interface to the underlying `I2C` instance
+    pub fn free(self) -> I2C {
+        self.interface.release()
+    }
+
+    /// Put the device into [`PowerMode::Sleep`] and return the raw interface
+    /// to the underlying `I2C` instance
+    ///
+    /// Unlike [`Icm42670::free`], this leaves the device drawing as little
+    /// current as possible. If the gyroscope is enabled it is kept running
+    /// for its minimum on-time of 45ms before being disabled.
+    pub fn shutdown<D, E>(mut self, delay: &mut D) -> Result<I2C, Error<E>>
+    where
+        I2C: Write<Error = E> + WriteRead<Error = E>,
+        E: Debug,
+        MODE: PowerState,
+        D: DelayMs<u8>,
+    {
+        self.sleep(delay)?;
+
+        Ok(self.free())
+    }
+}
+
+impl<'a, I2C, E> Icm42670<I2cRefInterface<'a, I2C>>
+where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+    E: Debug,
+{
+    /// Instantiate a new instance of the driver over a borrowed I²C
+    /// peripheral and initialize the device
+    ///
+    /// The bus is available to other drivers again once this driver has been
+    /// dropped, after which [`Icm42670::attach_borrowed`] resumes using the
+    /// device without reinitializing it.
+    pub fn new_borrowed(i2c: &'a mut I2C, address: Address) -> Result<Self, Error<E>> {
+        Self::with_interface(I2cRefInterface::new(i2c, address))
+    }
+
+    /// Instantiate a new instance of the driver over a borrowed I²C
+    /// peripheral, leaving the configuration of the device untouched
+    ///
+    /// See [`Icm42670::attach_interface`].
+    pub fn attach_borrowed(i2c: &'a mut I2C, address: Address) -> Result<Self, Error<E>> {
+        Self::attach_interface(I2cRefInterface::new(i2c, address))
+    }
+}
+
+impl<'a, I2C, MODE> Icm42670<I2cRefInterface<'a, I2C>, MODE> {
+    /// Return the borrowed `I2C` instance
+    pub fn free(self) -> &'a mut I2C {
+        self.interface.release()
+    }
+}
+
+impl<SPI, CS, SE, PE> Icm42670<SpiInterface<SPI, CS>>
+where
+    SPI: Transfer<u8, Error = SE> + SpiWrite<u8, Error = SE>,
+    CS: OutputPin<Error = PE>,
+    SE: Debug,
+    PE: Debug,
+{
+    /// Instantiate a new instance of the driver over SPI and initialize the
+    /// device
+    pub fn new_spi(spi: SPI, cs: CS) -> Result<Self, Error<SpiError<SE, PE>>> {
+        let mut me = Self::with_interface(SpiInterface::new(spi, cs))?;
+        me.disable_i2c()?;
+
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over SPI and initialize the
+    /// device, without verifying the device ID
+    ///
+    /// See [`Icm42670::with_interface_unchecked`].
+    pub fn new_spi_unchecked(spi: SPI, cs: CS) -> Result<Self, Error<SpiError<SE, PE>>> {
+        let mut me = Self::with_interface_unchecked(SpiInterface::new(spi, cs))?;
+        me.disable_i2c()?;
+
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over SPI and initialize the
+    /// device, accepting additional device IDs
+    ///
+    /// See [`Icm42670::with_interface_accepting`].
+    pub fn new_spi_accepting(
+        spi: SPI,
+        cs: CS,
+        device_ids: &[u8],
+    ) -> Result<(Self, u8), Error<SpiError<SE, PE>>> {
+        let (mut me, device_id) =
+            Self::with_interface_accepting(SpiInterface::new(spi, cs), device_ids)?;
+        me.disable_i2c()?;
+
+        Ok((me, device_id))
+    }
+
+    /// Instantiate a new instance of the driver over SPI and initialize the
+    /// device in the provided power mode
+    ///
+    /// See [`Icm42670::with_interface_and_power_mode`].
+    pub fn new_spi_with_power_mode(
+        spi: SPI,
+        cs: CS,
+        power_mode: Option<PowerMode>,
+    ) -> Result<Self, Error<SpiError<SE, PE>>> {
+        let mut me = Self::with_interface_and_power_mode(SpiInterface::new(spi, cs), power_mode)?;
+        me.disable_i2c()?;
+
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over SPI and initialize the
+    /// device, immediately following power-on
+    ///
+    /// See [`Icm42670::with_interface_and_delay`].
+    pub fn new_spi_with_delay<D>(
+        spi: SPI,
+        cs: CS,
+        delay: &mut D,
+        retries: u8,
+    ) -> Result<Self, Error<SpiError<SE, PE>>>
+    where
+        D: DelayUs<u8>,
+    {
+        let mut me = Self::with_interface_and_delay(SpiInterface::new(spi, cs), delay, retries)?;
+        me.disable_i2c()?;
+
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over SPI, leaving the
+    /// configuration of the device untouched
+    ///
+    /// The I²C interface is not disabled either, so this should only be used
+    /// to attach to a device which was previously brought up with
+    /// [`Icm42670::new_spi`] or similar. See [`Icm42670::attach_interface`].
+    pub fn attach_spi(spi: SPI, cs: CS) -> Result<Self, Error<SpiError<SE, PE>>> {
+        Self::attach_interface(SpiInterface::new(spi, cs))
+    }
+
+    /// Instantiate a new instance of the driver over SPI and bring the device
+    /// up in the provided configuration
+    ///
+    /// The configuration is validated using [`Config::validate`] before any
+    /// bus traffic occurs.
+    pub fn new_spi_with_config<D>(
+        spi: SPI,
+        cs: CS,
+        delay: &mut D,
+        config: &Config,
+    ) -> Result<Self, Error<SpiError<SE, PE>>>
+    where
+        D: DelayUs<u8>,
+    {
+        config.validate()?;
+
+        let mut me = Self::new_spi(spi, cs)?;
+        me.configure(delay, config)?;
+
+        Ok(me)
+    }
+
+    /// The I²C and SPI interfaces share pins, so disable the I²C interface to
+    /// prevent SPI traffic from being misinterpreted as I²C transactions.
+    fn disable_i2c(&mut self) -> Result<(), Error<SpiError<SE, PE>>> {
+        // Setting `UI_SIFS_CFG` to 0b11 disables the I²C interface
+        self.write_field(field::UI_SIFS_CFG, 0b11)
+    }
+}
+
+impl<SPI, CS, MODE> Icm42670<SpiInterface<SPI, CS>, MODE> {
+    /// Return the raw interfaces to the underlying `SPI` instance and chip
+    /// select pin
+    pub fn free(self) -> (SPI, CS) {
+        self.interface.release()
+    }
+
+    /// Put the device into [`PowerMode::Sleep`] and return the raw interfaces
+    /// to the underlying `SPI` instance and chip select pin
+    ///
+    /// Unlike [`Icm42670::free`], this leaves the device drawing as little
+    /// current as possible. If the gyroscope is enabled it is kept running
+    /// for its minimum on-time of 45ms before being disabled.
+    pub fn shutdown<D, SE, PE>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<(SPI, CS), Error<SpiError<SE, PE>>>
+    where
+        SPI: Transfer<u8, Error = SE> + SpiWrite<u8, Error = SE>,
+        CS: OutputPin<Error = PE>,
+        SE: Debug,
+        PE: Debug,
+        MODE: PowerState,
+        D: DelayMs<u8>,
+    {
+        self.sleep(delay)?;
+
+        Ok(self.free())
+    }
+}
+
+impl<DI, E> Icm42670<DI>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
 {
     /// Unique device identifiers for the ICM-42607 and ICM-42670
     ///
@@ -72,32 +503,291 @@ where
         0x67, // ICM-42670
     ];
 
-    /// Instantiate a new instance of the driver and initialize the device
-    pub fn new(i2c: I2C, address: Address) -> Result<Self, Error<E>> {
-        let mut me = Self { i2c, address };
+    /// Instantiate a new instance of the driver over any host interface and
+    /// initialize the device
+    ///
+    /// This allows for the use of transports other than those provided by
+    /// this crate; see [`Interface`] for more information.
+    pub fn with_interface(interface: DI) -> Result<Self, Error<E>> {
+        let (me, _) = Self::with_interface_accepting(interface, &[])?;
+
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over any host interface and
+    /// initialize the device, accepting the provided device IDs in addition
+    /// to [`Icm42670::DEVICE_IDS`]
+    ///
+    /// This allows for derivatives, such as the ICM-42670-S, which report a
+    /// different device ID but share the same register map. The detected
+    /// device ID is returned alongside the driver.
+    pub fn with_interface_accepting(
+        interface: DI,
+        device_ids: &[u8],
+    ) -> Result<(Self, u8), Error<E>> {
+        let (mut me, device_id) = Self::detect(interface)?;
+        Self::verify_device_id(device_id, device_ids)?;
+        me.init(Some(PowerMode::SixAxisLowNoise))?;
+
+        Ok((me, device_id))
+    }
+
+    /// Instantiate a new instance of the driver over any host interface and
+    /// initialize the device in the provided power mode
+    ///
+    /// The device is otherwise initialized as with
+    /// [`Icm42670::with_interface`]. If `power_mode` is `None` then the power
+    /// mode is left untouched, which following power-on is
+    /// [`PowerMode::Sleep`].
+    pub fn with_interface_and_power_mode(
+        interface: DI,
+        power_mode: Option<PowerMode>,
+    ) -> Result<Self, Error<E>> {
+        let (mut me, device_id) = Self::detect(interface)?;
+        Self::verify_device_id(device_id, &[])?;
+        me.init(power_mode)?;
 
-        // Verify that the device has the correct ID before continuing. If the ID does
-        // not match either of the expected values then it is likely the wrong chip is
-        // connected.
-        if !Self::DEVICE_IDS.contains(&me.device_id()?) {
-            return Err(Error::SensorError(SensorError::BadChip));
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over any host interface and
+    /// initialize the device, immediately following power-on
+    ///
+    /// The device is given the documented 1ms start-up time before its
+    /// device ID is read. If the bus reports an error, or the device ID reads
+    /// as `0x00` or `0xFF` as when nothing drives the bus, the read is
+    /// retried up to `retries` times at 1ms intervals. If no device responds
+    /// then [`SensorError::NoDeviceResponding`] is returned, whereas a device
+    /// responding with an unexpected ID results in
+    /// [`SensorError::UnexpectedDeviceId`].
+    /// The device is otherwise initialized as with
+    /// [`Icm42670::with_interface`].
+    pub fn with_interface_and_delay<D>(
+        interface: DI,
+        delay: &mut D,
+        retries: u8,
+    ) -> Result<Self, Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        let mut me = Self::from_interface(interface);
+
+        let mut device_id = None;
+        for _ in 0..=retries {
+            for _ in 0..4 {
+                delay.delay_us(250);
+            }
+
+            match me.device_id() {
+                Ok(id) if id != 0x00 && id != 0xFF => {
+                    device_id = Some(id);
+                    break;
+                }
+                _ => {}
+            }
         }
 
+        let device_id = device_id.ok_or(Error::SensorError(SensorError::NoDeviceResponding))?;
+        me.variant = DeviceVariant::from(device_id);
+
+        Self::verify_device_id(device_id, &[])?;
+        me.init(Some(PowerMode::SixAxisLowNoise))?;
+
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over any host interface,
+    /// leaving the configuration of the device untouched
+    ///
+    /// The device ID is verified as with [`Icm42670::with_interface`], but
+    /// rather than restoring the default configuration the driver adopts the
+    /// sensor data byte order and enabled APEX features from the device. This
+    /// allows resuming after a reset of the host alone, for example while
+    /// Wake on Motion remains armed. Any calibration or temperature
+    /// compensation model must be set again.
+    pub fn attach_interface(interface: DI) -> Result<Self, Error<E>> {
+        let (mut me, device_id) = Self::detect(interface)?;
+        Self::verify_device_id(device_id, &[])?;
+
+        me.data_endian = me.sensor_data_endian()?;
+
+        // Every APEX enable bit resides in `APEX_CONFIG1`, so read it only once
+        let apex = me.read_reg(&Bank0::APEX_CONFIG1)?;
+        let wom = me.read_reg(&Bank0::WOM_CONFIG)?;
+
+        me.apex = ApexFeatures {
+            pedometer: field::PED_ENABLE.get(apex) != 0,
+            tilt: field::TILT_ENABLE.get(apex) != 0,
+            smd: field::SMD_ENABLE.get(apex) != 0,
+            freefall: field::FF_ENABLE.get(apex) != 0,
+            wake_on_motion: field::WOM_EN.get(wom) != 0,
+        };
+
+        Ok(me)
+    }
+
+    /// Instantiate a new instance of the driver over any host interface and
+    /// initialize the device, without verifying the device ID
+    ///
+    /// This is intended for engineering samples, clones, and future members of
+    /// the family which report a different device ID but share the same
+    /// register map. The device ID is still read in order to determine the
+    /// [`DeviceVariant`], but no attempt is made to detect whether any device
+    /// is present at all, so prefer [`Icm42670::with_interface`] wherever
+    /// possible.
+    pub fn with_interface_unchecked(interface: DI) -> Result<Self, Error<E>> {
+        let (mut me, _) = Self::detect(interface)?;
+        me.init(Some(PowerMode::SixAxisLowNoise))?;
+
+        Ok(me)
+    }
+
+    /// Perform a software-reset on the device
+    ///
+    /// This returns as soon as the reset has been requested; see
+    /// [`Icm42670::soft_reset_and_wait`] for the complete reset sequence.
+    pub fn soft_reset(&mut self) -> Result<(), Error<E>> {
+        self.write_field(field::SOFT_RESET_DEVICE_CONFIG, 1)?;
+        self.apex = ApexFeatures::default();
+        self.data_endian = SensorDataEndian::default();
+        self.cached = CachedConfig::default();
+        self.pending_interrupts = [0; 4];
+        self.interrupt_clear = InterruptClearConfig::default();
+
+        Ok(())
+    }
+
+    /// Perform a software-reset on the device and wait for it to complete
+    ///
+    /// After requesting the reset, the device is given the documented 1ms to
+    /// reset before `INT_STATUS` is polled for the reset-done status, which
+    /// is cleared by reading it. Returns [`SensorError::ResetTimeout`] if the
+    /// reset does not complete within a further 1ms.
+    ///
+    /// If `reinitialize` is `false` the device is left in its reset state,
+    /// with both sensors disabled. Otherwise, the device is initialized as by
+    /// [`Icm42670::new`], the sensor data byte order is restored, and the
+    /// configuration most recently applied using [`Icm42670::configure`] or
+    /// [`Icm42670::apply_config`], if any, is applied again. APEX features
+    /// are never re-enabled.
+    pub fn soft_reset_and_wait<D>(
+        &mut self,
+        delay: &mut D,
+        reinitialize: bool,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        let endian = self.data_endian;
+
+        self.soft_reset()?;
+        for _ in 0..4 {
+            delay.delay_us(250);
+        }
+
+        let mut done = false;
+        for _ in 0..10 {
+            if self.take_interrupt(field::RESET_DONE_INT)? {
+                done = true;
+                break;
+            }
+            delay.delay_us(100);
+        }
+        if !done {
+            return Err(Error::SensorError(SensorError::ResetTimeout));
+        }
+
+        if reinitialize {
+            self.init(Some(PowerMode::SixAxisLowNoise))?;
+            self.set_sensor_data_endian(endian)?;
+            if let Some(config) = self.config {
+                self.configure(delay, &config)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the power mode of the IMU
+    pub fn set_power_mode(&mut self, mode: PowerMode) -> Result<(), Error<E>> {
+        self.update_reg(&Bank0::PWR_MGMT0, mode.bits(), PowerMode::BITMASK)
+    }
+
+    /// Create the driver and read the device ID, without otherwise touching
+    /// the device
+    fn detect(interface: DI) -> Result<(Self, u8), Error<E>> {
+        let mut me = Self::from_interface(interface);
+
+        let device_id = me.device_id()?;
+        me.variant = DeviceVariant::from(device_id);
+
+        Ok((me, device_id))
+    }
+
+    /// Create the driver without touching the device
+    fn from_interface(interface: DI) -> Self {
+        Self {
+            interface,
+            apex: ApexFeatures::default(),
+            data_endian: SensorDataEndian::default(),
+            cached: CachedConfig::default(),
+            pending_interrupts: [0; 4],
+            interrupt_clear: InterruptClearConfig::default(),
+            calibration: Calibration::default(),
+            gyro_temp_model: None,
+            variant: DeviceVariant::Unknown(0),
+            config: None,
+            observer: None,
+            mode: PhantomData,
+        }
+    }
+
+    /// Verify that the device ID is either one of [`Icm42670::DEVICE_IDS`] or
+    /// one of the provided additional IDs
+    fn verify_device_id(device_id: u8, device_ids: &[u8]) -> Result<(), Error<E>> {
+        // If the ID does not match any of the expected values then it is likely the
+        // wrong chip is connected.
+        if Self::DEVICE_IDS.contains(&device_id) || device_ids.contains(&device_id) {
+            Ok(())
+        } else {
+            Err(Error::SensorError(SensorError::UnexpectedDeviceId(
+                device_id,
+            )))
+        }
+    }
+
+    /// Initialize a device whose ID has already been verified, if required
+    fn init(&mut self, power_mode: Option<PowerMode>) -> Result<(), Error<E>> {
         // Make sure that any configuration has been restored to the default values when
         // initializing the driver.
-        me.set_accel_range(AccelRange::default())?;
-        me.set_gyro_range(GyroRange::default())?;
+        self.set_accel_range(AccelRange::default())?;
+        self.set_gyro_range(GyroRange::default())?;
+        self.set_sensor_data_endian(SensorDataEndian::default())?;
 
         // The IMU uses `PowerMode::Sleep` by default, which disables both the accel and
-        // gyro, so we enable them both during driver initialization.
-        me.set_power_mode(PowerMode::SixAxisLowNoise)?;
+        // gyro, so by default we enable them both during driver initialization.
+        if let Some(power_mode) = power_mode {
+            self.set_power_mode(power_mode)?;
+        }
 
-        Ok(me)
+        Ok(())
     }
+}
 
-    /// Return the raw interface to the underlying `I2C` instance
-    pub fn free(self) -> I2C {
-        self.i2c
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Return the APEX features which have been enabled via the driver
+    pub fn apex_features(&self) -> ApexFeatures {
+        self.apex
+    }
+
+    /// Return the underlying host interface
+    pub fn into_interface(self) -> DI {
+        self.interface
     }
 
     /// Read the ID of the connected device
@@ -105,13 +795,51 @@ where
         self.read_reg(&Bank0::WHO_AM_I)
     }
 
-    /// Perform a software-reset on the device
-    pub fn soft_reset(&mut self) -> Result<(), Error<E>> {
-        self.update_reg(&Bank0::SIGNAL_PATH_RESET, 0x10, 0b0001_0000)
+    /// Return the part which was detected on the bus when the driver was
+    /// instantiated
+    pub fn variant(&self) -> DeviceVariant {
+        self.variant
+    }
+
+    /// Return the normalized accelerometer data for each of the three axes
+    ///
+    /// Any calibration loaded via [`Icm42670::set_calibration`] is applied.
+    pub fn accel_norm(&mut self) -> Result<F32x3, Error<E>>
+    where
+        MODE: AccelEnabled,
+    {
+        let range = self.accel_range()?;
+        let scale = range.scale_factor();
+
+        // Scale the raw Accelerometer data using the appropriate factor based on the
+        // configured range.
+        let raw = self.accel_raw()?;
+        let x = raw.x as f32 / scale;
+        let y = raw.y as f32 / scale;
+        let z = raw.z as f32 / scale;
+
+        Ok(self.calibration.accel.apply(F32x3::new(x, y, z)))
+    }
+
+    /// Read the raw accelerometer data for each of the three axes
+    ///
+    /// Returns [`SensorError::InvalidSample`] while the accelerometer is
+    /// disabled or starting up.
+    pub fn accel_raw(&mut self) -> Result<I16x3, Error<E>>
+    where
+        MODE: AccelEnabled,
+    {
+        self.read_reg_vector(&Bank0::ACCEL_DATA_X1)
     }
 
     /// Return the normalized gyro data for each of the three axes
-    pub fn gyro_norm(&mut self) -> Result<F32x3, Error<E>> {
+    ///
+    /// Any temperature compensation set via [`Icm42670::set_gyro_temp_model`]
+    /// and calibration loaded via [`Icm42670::set_calibration`] are applied.
+    pub fn gyro_norm(&mut self) -> Result<F32x3, Error<E>>
+    where
+        MODE: GyroEnabled,
+    {
         let range = self.gyro_range()?;
         let scale = range.scale_factor();
 
@@ -122,16 +850,23 @@ where
         let y = raw.y as f32 / scale;
         let z = raw.z as f32 / scale;
 
-        Ok(F32x3::new(x, y, z))
+        let mut gyro = F32x3::new(x, y, z);
+        if let Some(model) = self.gyro_temp_model {
+            gyro = model.compensate(gyro, self.temperature()?);
+        }
+
+        Ok(self.calibration.gyro.apply(gyro))
     }
 
     /// Read the raw gyro data for each of the three axes
-    pub fn gyro_raw(&mut self) -> Result<I16x3, Error<E>> {
-        let x = self.read_reg_i16(&Bank0::GYRO_DATA_X1, &Bank0::GYRO_DATA_X0)?;
-        let y = self.read_reg_i16(&Bank0::GYRO_DATA_Y1, &Bank0::GYRO_DATA_Y0)?;
-        let z = self.read_reg_i16(&Bank0::GYRO_DATA_Z1, &Bank0::GYRO_DATA_Z0)?;
-
-        Ok(I16x3::new(x, y, z))
+    ///
+    /// Returns [`SensorError::InvalidSample`] while the gyroscope is disabled
+    /// or starting up.
+    pub fn gyro_raw(&mut self) -> Result<I16x3, Error<E>>
+    where
+        MODE: GyroEnabled,
+    {
+        self.read_reg_vector(&Bank0::GYRO_DATA_X1)
     }
 
     /// Read the built-in temperature sensor and return the value in degrees
@@ -145,127 +880,349 @@ where
 
     /// Read the raw data from the built-in temperature sensor
     pub fn temperature_raw(&mut self) -> Result<i16, Error<E>> {
-        self.read_reg_i16(&Bank0::TEMP_DATA1, &Bank0::TEMP_DATA0)
+        match self.read_reg_i16(&Bank0::TEMP_DATA1)? {
+            data::INVALID_SAMPLE => Err(Error::SensorError(SensorError::InvalidSample)),
+            raw => Ok(raw),
+        }
     }
 
     /// Return the currently configured power mode
     pub fn power_mode(&mut self) -> Result<PowerMode, Error<E>> {
-        //  `GYRO_MODE` occupies bits 3:2 in the register
-        // `ACCEL_MODE` occupies bits 1:0 in the register
-        let bits = self.read_reg(&Bank0::PWR_MGMT0)? & 0xF;
+        // `GYRO_MODE` and `ACCEL_MODE` together form the power mode
+        let bits = self.read_reg(&Bank0::PWR_MGMT0)? & PowerMode::BITMASK;
         let mode = PowerMode::try_from(bits)?;
 
         Ok(mode)
     }
 
-    /// Set the power mode of the IMU
-    pub fn set_power_mode(&mut self, mode: PowerMode) -> Result<(), Error<E>> {
-        self.update_reg(&Bank0::PWR_MGMT0, mode.bits(), PowerMode::BITMASK)
-    }
-
     /// Return the currently configured accelerometer range
+    ///
+    /// The range is only read from the device if it is not already cached;
+    /// see [`Icm42670::refresh_config`].
     pub fn accel_range(&mut self) -> Result<AccelRange, Error<E>> {
-        // `ACCEL_UI_FS_SEL` occupies bits 6:5 in the register
-        let fs_sel = self.read_reg(&Bank0::ACCEL_CONFIG0)? >> 5;
+        if let Some(range) = self.cached.accel_range {
+            return Ok(range);
+        }
+
+        let fs_sel = self.read_field(field::ACCEL_UI_FS_SEL)?;
         let range = AccelRange::try_from(fs_sel)?;
+        self.cached.accel_range = Some(range);
 
         Ok(range)
     }
 
     /// Set the range of the accelerometer
     pub fn set_accel_range(&mut self, range: AccelRange) -> Result<(), Error<E>> {
-        self.update_reg(&Bank0::ACCEL_CONFIG0, range.bits(), AccelRange::BITMASK)
+        self.cached.accel_range = None;
+        self.update_reg(&Bank0::ACCEL_CONFIG0, range.bits(), AccelRange::BITMASK)?;
+        self.cached.accel_range = Some(range);
+
+        Ok(())
     }
 
     /// Return the currently configured gyroscope range
+    ///
+    /// The range is only read from the device if it is not already cached;
+    /// see [`Icm42670::refresh_config`].
     pub fn gyro_range(&mut self) -> Result<GyroRange, Error<E>> {
-        // `GYRO_UI_FS_SEL` occupies bits 6:5 in the register
-        let fs_sel = self.read_reg(&Bank0::GYRO_CONFIG0)? >> 5;
+        if let Some(range) = self.cached.gyro_range {
+            return Ok(range);
+        }
+
+        let fs_sel = self.read_field(field::GYRO_UI_FS_SEL)?;
         let range = GyroRange::try_from(fs_sel)?;
+        self.cached.gyro_range = Some(range);
 
         Ok(range)
     }
 
     /// Set the range of the gyro
     pub fn set_gyro_range(&mut self, range: GyroRange) -> Result<(), Error<E>> {
-        self.update_reg(&Bank0::GYRO_CONFIG0, range.bits(), GyroRange::BITMASK)
+        self.cached.gyro_range = None;
+        self.update_reg(&Bank0::GYRO_CONFIG0, range.bits(), GyroRange::BITMASK)?;
+        self.cached.gyro_range = Some(range);
+
+        Ok(())
     }
 
     /// Return the currently configured output data rate for the accelerometer
+    ///
+    /// The output data rate is only read from the device if it is not already
+    /// cached; see [`Icm42670::refresh_config`].
     pub fn accel_odr(&mut self) -> Result<AccelOdr, Error<E>> {
-        // `ACCEL_ODR` occupies bits 3:0 in the register
-        let odr = self.read_reg(&Bank0::ACCEL_CONFIG0)? & 0xF;
+        if let Some(odr) = self.cached.accel_odr {
+            return Ok(odr);
+        }
+
+        let odr = self.read_field(field::ACCEL_ODR)?;
         let odr = AccelOdr::try_from(odr)?;
+        self.cached.accel_odr = Some(odr);
 
         Ok(odr)
     }
 
     /// Set the output data rate of the accelerometer
     pub fn set_accel_odr(&mut self, odr: AccelOdr) -> Result<(), Error<E>> {
-        self.update_reg(&Bank0::ACCEL_CONFIG0, odr.bits(), AccelOdr::BITMASK)
+        self.cached.accel_odr = None;
+        self.update_reg(&Bank0::ACCEL_CONFIG0, odr.bits(), AccelOdr::BITMASK)?;
+        self.cached.accel_odr = Some(odr);
+
+        Ok(())
+    }
+
+    /// Return the currently configured low-pass filter bandwidth for the
+    /// accelerometer
+    pub fn accel_lpf(&mut self) -> Result<AccelLpf, Error<E>> {
+        let bw = self.read_field(field::ACCEL_UI_FILT_BW)?;
+        let bw = AccelLpf::try_from(bw)?;
+
+        Ok(bw)
+    }
+
+    /// Set the low-pass filter bandwidth of the accelerometer
+    pub fn set_accel_lpf(&mut self, lpf: AccelLpf) -> Result<(), Error<E>> {
+        self.update_reg(&Bank0::ACCEL_CONFIG1, lpf.bits(), AccelLpf::BITMASK)
     }
 
     /// Return the currently configured output data rate for the gyroscope
+    ///
+    /// The output data rate is only read from the device if it is not already
+    /// cached; see [`Icm42670::refresh_config`].
     pub fn gyro_odr(&mut self) -> Result<GyroOdr, Error<E>> {
-        // `GYRO_ODR` occupies bits 3:0 in the register
-        let odr = self.read_reg(&Bank0::GYRO_CONFIG0)? & 0xF;
+        if let Some(odr) = self.cached.gyro_odr {
+            return Ok(odr);
+        }
+
+        let odr = self.read_field(field::GYRO_ODR)?;
         let odr = GyroOdr::try_from(odr)?;
+        self.cached.gyro_odr = Some(odr);
 
         Ok(odr)
     }
 
     /// Set the output data rate of the gyroscope
     pub fn set_gyro_odr(&mut self, odr: GyroOdr) -> Result<(), Error<E>> {
-        self.update_reg(&Bank0::GYRO_CONFIG0, odr.bits(), GyroOdr::BITMASK)
+        self.cached.gyro_odr = None;
+        self.update_reg(&Bank0::GYRO_CONFIG0, odr.bits(), GyroOdr::BITMASK)?;
+        self.cached.gyro_odr = Some(odr);
+
+        Ok(())
+    }
+
+    /// Read the ranges and output data rates of both sensors from the device,
+    /// replacing those cached by the driver
+    ///
+    /// The driver caches these once they have been read or set, so that
+    /// normalizing each sample does not require another read of the
+    /// configuration registers. If they may have been changed by other means,
+    /// such as by raw register access or by another host sharing the device,
+    /// this must be called before reading any further normalized data.
+    pub fn refresh_config(&mut self) -> Result<(), Error<E>> {
+        self.cached = CachedConfig::default();
+
+        let accel = self.read_reg(&Bank0::ACCEL_CONFIG0)?;
+        let gyro = self.read_reg(&Bank0::GYRO_CONFIG0)?;
+
+        self.cached = CachedConfig {
+            accel_range: Some(AccelRange::try_from(field::ACCEL_UI_FS_SEL.get(accel))?),
+            gyro_range: Some(GyroRange::try_from(field::GYRO_UI_FS_SEL.get(gyro))?),
+            accel_odr: Some(AccelOdr::try_from(field::ACCEL_ODR.get(accel))?),
+            gyro_odr: Some(GyroOdr::try_from(field::GYRO_ODR.get(gyro))?),
+        };
+
+        Ok(())
+    }
+
+    /// Return the currently configured low-pass filter bandwidth for the
+    /// gyroscope
+    pub fn gyro_lpf(&mut self) -> Result<GyroLpf, Error<E>> {
+        let bw = self.read_field(field::GYRO_UI_FILT_BW)?;
+        let bw = GyroLpf::try_from(bw)?;
+
+        Ok(bw)
+    }
+
+    /// Set the low-pass filter bandwidth of the gyroscope
+    ///
+    /// A lower bandwidth reduces noise at the cost of additional latency.
+    pub fn set_gyro_lpf(&mut self, lpf: GyroLpf) -> Result<(), Error<E>> {
+        self.update_reg(&Bank0::GYRO_CONFIG1, lpf.bits(), GyroLpf::BITMASK)
+    }
+
+    /// Return the currently configured sensor data byte order
+    pub fn sensor_data_endian(&mut self) -> Result<SensorDataEndian, Error<E>> {
+        let bits = self.read_field(field::SENSOR_DATA_ENDIAN)?;
+        let endian = SensorDataEndian::try_from(bits)?;
+
+        Ok(endian)
+    }
+
+    /// Set the byte order in which sensor data is reported
+    ///
+    /// The byte order is retained by the driver, so that data read from the
+    /// sensor data registers or the FIFO is always interpreted correctly.
+    pub fn set_sensor_data_endian(&mut self, endian: SensorDataEndian) -> Result<(), Error<E>> {
+        self.update_reg(
+            &Bank0::INTF_CONFIG0,
+            endian.bits(),
+            SensorDataEndian::BITMASK,
+        )?;
+        self.data_endian = endian;
+
+        Ok(())
+    }
+
+    /// Return the function currently assigned to pin 9
+    pub fn pin9_function<D>(&mut self, delay: &mut D) -> Result<Pin9Function, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let bits = self.read_mfield(delay, field::PIN9_FUNCTION)?;
+        let function = Pin9Function::try_from(bits)?;
+
+        Ok(function)
+    }
+
+    /// Assign a function to pin 9
+    ///
+    /// Pin 9 is shared between `INT2`, `FSYNC` and `CLKIN`, so only one of
+    /// these may be used at a time. Any interrupts routed to `INT2` are not
+    /// signalled unless pin 9 is assigned [`Pin9Function::Int2`].
+    pub fn set_pin9_function<D>(
+        &mut self,
+        delay: &mut D,
+        function: Pin9Function,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::INTF_CONFIG5,
+            function.bits(),
+            Pin9Function::BITMASK,
+        )
+    }
+
+    /// Is the device being clocked from an external clock on `CLKIN`?
+    pub fn clkin_enabled<D>(&mut self, delay: &mut D) -> Result<bool, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let rtc_mode = self.read_field(field::RTC_MODE)? != 0;
+        let clkin = self.pin9_function(delay)? == Pin9Function::Clkin;
+
+        Ok(rtc_mode && clkin)
+    }
+
+    /// Clock the device from an external 32.768 kHz clock applied to pin 9
+    ///
+    /// This assigns [`Pin9Function::Clkin`] to pin 9 and enables RTC mode, so
+    /// `INT2` and `FSYNC` are unavailable while the external clock is in use.
+    ///
+    /// In RTC mode the output data rates are derived from the external clock
+    /// rather than from the internal oscillator, so their accuracy is that of
+    /// the supplied clock. Driving several devices from a common clock keeps
+    /// their samples aligned without drifting relative to one another. A clock
+    /// which deviates from 32.768 kHz shifts every ODR by the same proportion.
+    pub fn enable_clkin<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.set_pin9_function(delay, Pin9Function::Clkin)?;
+        self.write_field(field::RTC_MODE, 1)
+    }
+
+    /// Return to clocking the device from its internal oscillator
+    ///
+    /// This disables RTC mode and assigns [`Pin9Function::Int2`] to pin 9.
+    pub fn disable_clkin<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.write_field(field::RTC_MODE, 0)?;
+        self.set_pin9_function(delay, Pin9Function::Int2)
     }
 
     // -----------------------------------------------------------------------
     // PRIVATE
 
+    /// Put the device into [`PowerMode::Sleep`], respecting the minimum
+    /// on-time of the gyroscope
+    fn sleep<D>(&mut self, delay: &mut D) -> Result<(), Error<E>>
+    where
+        D: DelayMs<u8>,
+    {
+        // Once enabled, the gyroscope must be kept running for at least 45ms. We
+        // cannot know when it was enabled, so always wait for the full duration.
+        if matches!(
+            self.power_mode()?,
+            PowerMode::Standby | PowerMode::GyroLowNoise | PowerMode::SixAxisLowNoise
+        ) {
+            delay.delay_ms(45);
+        }
+
+        self.update_reg(
+            &Bank0::PWR_MGMT0,
+            PowerMode::Sleep.bits(),
+            PowerMode::BITMASK,
+        )
+    }
+
     // FIXME: 'Sleep mode' and 'accelerometer low power mode with WUOSC' do not
     //        support MREG1, MREG2 or MREG3 access.
-    #[allow(unused)]
-    fn read_mreg(
+    fn read_mreg<R>(
         &mut self,
         delay: &mut dyn DelayUs<u8>,
         bank: RegisterBank,
-        reg: &dyn Register,
-    ) -> Result<u8, Error<E>> {
+        reg: &R,
+    ) -> Result<u8, Error<E>>
+    where
+        R: Register,
+    {
         // See "ACCESSING MREG1, MREG2 AND MREG3 REGISTERS" (page 40)
 
-        // Wait until the internal clock is running prior to writing.
-        while self.read_reg(&Bank0::MCLK_RDY)? != 0x1 {}
+        self.wait_for_mclk(delay)?;
 
         // Select the appropriate block and set the register address to read from.
         self.write_reg(&Bank0::BLK_SEL_R, bank.blk_sel())?;
         self.write_reg(&Bank0::MADDR_R, reg.addr())?;
-        delay.delay_us(10);
+        Self::mreg_wait(delay);
 
         // Read a value from the register.
         let result = self.read_reg(&Bank0::M_R)?;
-        delay.delay_us(10);
+        Self::mreg_wait(delay);
 
         // Reset block selection registers.
         self.write_reg(&Bank0::BLK_SEL_R, 0x00)?;
         self.write_reg(&Bank0::BLK_SEL_W, 0x00)?;
 
+        self.observe_read(bank.into(), reg.addr(), &[result]);
+
         Ok(result)
     }
 
     // FIXME: 'Sleep mode' and 'accelerometer low power mode with WUOSC' do not
     //        support MREG1, MREG2 or MREG3 access.
-    #[allow(unused)]
-    fn write_mreg(
+    fn write_mreg<R>(
         &mut self,
         delay: &mut dyn DelayUs<u8>,
         bank: RegisterBank,
-        reg: &dyn Register,
+        reg: &R,
         value: u8,
-    ) -> Result<(), Error<E>> {
+    ) -> Result<(), Error<E>>
+    where
+        R: Register,
+    {
         // See "ACCESSING MREG1, MREG2 AND MREG3 REGISTERS" (page 40)
 
-        // Wait until the internal clock is running prior to writing.
-        while self.read_reg(&Bank0::MCLK_RDY)? != 0x1 {}
+        self.wait_for_mclk(delay)?;
 
         // Select the appropriate block and set the register address to write to.
         self.write_reg(&Bank0::BLK_SEL_W, bank.blk_sel())?;
@@ -273,47 +1230,241 @@ where
 
         // Write the value to the register.
         self.write_reg(&Bank0::M_W, value)?;
-        delay.delay_us(10);
+        Self::mreg_wait(delay);
 
         // Reset block selection registers.
         self.write_reg(&Bank0::BLK_SEL_R, 0x00)?;
         self.write_reg(&Bank0::BLK_SEL_W, 0x00)?;
 
+        self.observe_write(bank.into(), reg.addr(), value);
+
         Ok(())
     }
 
+    /// Read consecutive registers in one of the MREG banks, starting at the
+    /// provided address.
+    ///
+    /// The block selection is only written once, and the device increments
+    /// `MADDR_R` after each read of `M_R`, avoiding the overhead of reading
+    /// each register individually.
+    fn read_mregs<R>(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        bank: RegisterBank,
+        reg: &R,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<E>>
+    where
+        R: Register,
+    {
+        self.wait_for_mclk(delay)?;
+
+        self.write_reg(&Bank0::BLK_SEL_R, bank.blk_sel())?;
+        self.write_reg(&Bank0::MADDR_R, reg.addr())?;
+        Self::mreg_wait(delay);
+
+        // `M_R` must be read one byte at a time, as a burst read over the bus
+        // would advance to the following Bank 0 register instead.
+        for byte in buffer.iter_mut() {
+            *byte = self.read_reg(&Bank0::M_R)?;
+            Self::mreg_wait(delay);
+        }
+
+        self.write_reg(&Bank0::BLK_SEL_R, 0x00)?;
+        self.write_reg(&Bank0::BLK_SEL_W, 0x00)?;
+
+        self.observe_read(bank.into(), reg.addr(), buffer);
+
+        Ok(())
+    }
+
+    /// Write consecutive registers in one of the MREG banks, starting at the
+    /// provided address.
+    ///
+    /// The block selection is only written once, and the device increments
+    /// `MADDR_W` after each write to `M_W`.
+    fn write_mregs<R>(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        bank: RegisterBank,
+        reg: &R,
+        values: &[u8],
+    ) -> Result<(), Error<E>>
+    where
+        R: Register,
+    {
+        if reg.read_only() {
+            return Err(Error::SensorError(SensorError::WriteToReadOnly));
+        }
+
+        self.wait_for_mclk(delay)?;
+
+        self.write_reg(&Bank0::BLK_SEL_W, bank.blk_sel())?;
+        self.write_reg(&Bank0::MADDR_W, reg.addr())?;
+
+        for (i, value) in values.iter().enumerate() {
+            self.write_reg(&Bank0::M_W, *value)?;
+            Self::mreg_wait(delay);
+
+            self.observe_write(bank.into(), reg.addr().wrapping_add(i as u8), *value);
+        }
+
+        self.write_reg(&Bank0::BLK_SEL_R, 0x00)?;
+        self.write_reg(&Bank0::BLK_SEL_W, 0x00)?;
+
+        Ok(())
+    }
+
+    /// Wait until the internal clock is running, as is required prior to
+    /// accessing the MREG banks.
+    ///
+    /// Rather than hanging should the clock never start, for example because
+    /// the device is asleep, give up after roughly 1ms.
+    fn wait_for_mclk(&mut self, delay: &mut dyn DelayUs<u8>) -> Result<(), Error<E>> {
+        for _ in 0..100 {
+            if self.read_reg(&Bank0::MCLK_RDY)? == 0x1 {
+                return Ok(());
+            }
+            delay.delay_us(10);
+        }
+
+        Err(Error::SensorError(SensorError::MclkTimeout))
+    }
+
+    /// Wait out the 10µs required between successive accesses to the MREG
+    /// banks, less the time which the interface guarantees each access takes.
+    fn mreg_wait(delay: &mut dyn DelayUs<u8>) {
+        let wait = 10u8.saturating_sub(DI::MIN_ACCESS_US);
+        if wait > 0 {
+            delay.delay_us(wait);
+        }
+    }
+
+    /// Update the register at the provided address in one of the MREG banks.
+    ///
+    /// See [`Self::update_reg`] for more information.
+    fn update_mreg<R>(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        bank: RegisterBank,
+        reg: &R,
+        value: u8,
+        mask: u8,
+    ) -> Result<(), Error<E>>
+    where
+        R: Register,
+    {
+        if reg.read_only() {
+            Err(Error::SensorError(SensorError::WriteToReadOnly))
+        } else {
+            let current = self.read_mreg(delay, bank, reg)?;
+            let value = (current & !mask) | (value & mask);
+
+            self.write_mreg(delay, bank, reg, value)
+        }
+    }
+
+    /// Read the value of a field in MREG1.
+    fn read_mfield(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        field: Field<Mreg1>,
+    ) -> Result<u8, Error<E>> {
+        let bits = self.read_mreg(delay, RegisterBank::MReg1, &field.reg)?;
+
+        Ok(field.get(bits))
+    }
+
+    /// Set a field in MREG1 to the given value, leaving the remainder of its
+    /// register unchanged.
+    fn write_mfield(
+        &mut self,
+        delay: &mut dyn DelayUs<u8>,
+        field: Field<Mreg1>,
+        value: u8,
+    ) -> Result<(), Error<E>> {
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &field.reg,
+            field.bits(value),
+            field.mask(),
+        )
+    }
+
     /// Read a register at the provided address.
-    fn read_reg(&mut self, reg: &dyn Register) -> Result<u8, Error<E>> {
-        let mut buffer = [0u8];
-        self.i2c
-            .write_read(self.address as u8, &[reg.addr()], &mut buffer)
+    fn read_reg<R>(&mut self, reg: &R) -> Result<u8, Error<E>>
+    where
+        R: Register,
+    {
+        let value = self
+            .interface
+            .read_reg(reg.addr())
             .map_err(|e| Error::BusError(e))?;
+        self.observe_read(Bank::Bank0, reg.addr(), &[value]);
 
-        Ok(buffer[0])
+        Ok(value)
     }
 
-    /// Read two registers and combine them into a single value.
-    fn read_reg_i16(
-        &mut self,
-        reg_hi: &dyn Register,
-        reg_lo: &dyn Register,
-    ) -> Result<i16, Error<E>> {
-        let data_hi = self.read_reg(reg_hi)?;
-        let data_lo = self.read_reg(reg_lo)?;
+    /// Read consecutive registers starting at the provided address.
+    fn read_regs<R>(&mut self, reg: &R, buffer: &mut [u8]) -> Result<(), Error<E>>
+    where
+        R: Register,
+    {
+        self.interface
+            .read_regs(reg.addr(), buffer)
+            .map_err(|e| Error::BusError(e))?;
+        self.observe_read(Bank::Bank0, reg.addr(), buffer);
+
+        Ok(())
+    }
 
-        let data = i16::from_be_bytes([data_hi, data_lo]);
+    /// Read two consecutive registers, starting with the high byte, and combine
+    /// them into a single value.
+    ///
+    /// Both registers are read in a single transaction, so that the high and
+    /// low bytes always belong to the same sample.
+    fn read_reg_i16<R>(&mut self, reg_hi: &R) -> Result<i16, Error<E>>
+    where
+        R: Register,
+    {
+        let mut buffer = [0u8; 2];
+        self.read_regs(reg_hi, &mut buffer)?;
+
+        let data = self.data_endian.word(buffer);
 
         Ok(data)
     }
 
+    /// Read six consecutive registers, starting with the high byte of the X
+    /// axis, and combine them into a vector.
+    ///
+    /// Returns an error if any axis holds an invalid sample.
+    fn read_reg_vector<R>(&mut self, reg_x_hi: &R) -> Result<I16x3, Error<E>>
+    where
+        R: Register,
+    {
+        let mut buffer = [0u8; 6];
+        self.read_regs(reg_x_hi, &mut buffer)?;
+
+        data::valid(data::vector(&buffer, self.data_endian))
+            .ok_or(Error::SensorError(SensorError::InvalidSample))
+    }
+
     /// Set a register at the provided address to a given value.
-    fn write_reg(&mut self, reg: &dyn Register, value: u8) -> Result<(), Error<E>> {
+    fn write_reg<R>(&mut self, reg: &R, value: u8) -> Result<(), Error<E>>
+    where
+        R: Register,
+    {
         if reg.read_only() {
             Err(Error::SensorError(SensorError::WriteToReadOnly))
         } else {
-            self.i2c
-                .write(self.address as u8, &[reg.addr(), value])
-                .map_err(|e| Error::BusError(e))
+            self.interface
+                .write_reg(reg.addr(), value)
+                .map_err(|e| Error::BusError(e))?;
+            self.observe_write(Bank::Bank0, reg.addr(), value);
+
+            Ok(())
         }
     }
 
@@ -322,7 +1473,10 @@ where
     /// Rather than overwriting any active bits in the register, we first read
     /// in its current value and then update it accordingly using the given
     /// value and mask before writing back the desired value.
-    fn update_reg(&mut self, reg: &dyn Register, value: u8, mask: u8) -> Result<(), Error<E>> {
+    fn update_reg<R>(&mut self, reg: &R, value: u8, mask: u8) -> Result<(), Error<E>>
+    where
+        R: Register,
+    {
         if reg.read_only() {
             Err(Error::SensorError(SensorError::WriteToReadOnly))
         } else {
@@ -332,27 +1486,53 @@ where
             self.write_reg(reg, value)
         }
     }
+
+    /// Update the register at the provided address, but only write to it if
+    /// its value would change.
+    ///
+    /// Returns whether or not the register was written.
+    fn update_reg_if_changed<R>(&mut self, reg: &R, value: u8, mask: u8) -> Result<bool, Error<E>>
+    where
+        R: Register,
+    {
+        let current = self.read_reg(reg)?;
+        let value = (current & !mask) | (value & mask);
+
+        if value == current {
+            Ok(false)
+        } else {
+            self.write_reg(reg, value)?;
+            Ok(true)
+        }
+    }
+
+    /// Read the value of a field in bank 0.
+    fn read_field(&mut self, field: Field<Bank0>) -> Result<u8, Error<E>> {
+        let bits = self.read_reg(&field.reg)?;
+
+        Ok(field.get(bits))
+    }
+
+    /// Set a field in bank 0 to the given value, leaving the remainder of its
+    /// register unchanged.
+    fn write_field(&mut self, field: Field<Bank0>, value: u8) -> Result<(), Error<E>> {
+        self.update_reg(&field.reg, field.bits(value), field.mask())
+    }
 }
 
-impl<I2C, E> Accelerometer for Icm42670<I2C>
+#[cfg(feature = "accelerometer")]
+impl<DI, E, MODE> Accelerometer for Icm42670<DI, MODE>
 where
-    I2C: Write<Error = E> + WriteRead<Error = E>,
+    DI: Interface<Error = E>,
     E: Debug,
+    MODE: AccelEnabled,
 {
     type Error = Error<E>;
 
     fn accel_norm(&mut self) -> Result<F32x3, AccelerometerError<Self::Error>> {
-        let range = self.accel_range()?;
-        let scale = range.scale_factor();
+        let data = Icm42670::accel_norm(self)?;
 
-        // Scale the raw Accelerometer data using the appropriate factor based on the
-        // configured range.
-        let raw = self.accel_raw()?;
-        let x = raw.x as f32 / scale;
-        let y = raw.y as f32 / scale;
-        let z = raw.z as f32 / scale;
-
-        Ok(F32x3::new(x, y, z))
+        Ok(data)
     }
 
     fn sample_rate(&mut self) -> Result<f32, AccelerometerError<Self::Error>> {
@@ -363,18 +1543,18 @@ where
     }
 }
 
-impl<I2C, E> RawAccelerometer<I16x3> for Icm42670<I2C>
+#[cfg(feature = "accelerometer")]
+impl<DI, E, MODE> RawAccelerometer<I16x3> for Icm42670<DI, MODE>
 where
-    I2C: Write<Error = E> + WriteRead<Error = E>,
+    DI: Interface<Error = E>,
     E: Debug,
+    MODE: AccelEnabled,
 {
     type Error = Error<E>;
 
     fn accel_raw(&mut self) -> Result<I16x3, AccelerometerError<Self::Error>> {
-        let x = self.read_reg_i16(&Bank0::ACCEL_DATA_X1, &Bank0::ACCEL_DATA_X0)?;
-        let y = self.read_reg_i16(&Bank0::ACCEL_DATA_Y1, &Bank0::ACCEL_DATA_Y0)?;
-        let z = self.read_reg_i16(&Bank0::ACCEL_DATA_Z1, &Bank0::ACCEL_DATA_Z0)?;
+        let data = Icm42670::accel_raw(self)?;
 
-        Ok(I16x3::new(x, y, z))
+        Ok(data)
     }
 }
@@ -0,0 +1,195 @@
+//! Type-level power modes
+//!
+//! By default the driver tracks nothing about the power mode of the device,
+//! which may be changed at any time using [`Icm42670::set_power_mode`]; this
+//! is the [`Dynamic`] mode. Alternatively, the power mode may be encoded in the
+//! type of the driver using [`Icm42670::into_mode`], in which case any
+//! operation which is invalid in that mode becomes a compile-time error:
+//!
+//! - Accelerometer data can only be read in modes which enable the
+//!   accelerometer; see [`AccelEnabled`]
+//! - Gyroscope data can only be read in modes which enable the gyroscope; see
+//!   [`GyroEnabled`]
+//! - Any configuration residing in the MREG banks can only be accessed in modes
+//!   which run the internal clock; see [`MregAccessible`]. Such methods take a
+//!   `delay`, though not every method taking a `delay` accesses the MREG banks
+//!
+//! Operations which change the power mode behind the driver's back, such as
+//! [`Icm42670::soft_reset`] and [`Icm42670::enable_wake_on_motion`], are only
+//! available in the [`Dynamic`] mode.
+
+use core::{fmt::Debug, marker::PhantomData};
+
+use embedded_hal::blocking::delay::DelayMs;
+
+use crate::{config::Bitfield, register::Bank0, Error, Icm42670, Interface, PowerMode};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Power mode of the driver, either [`Dynamic`] or one of the fixed power
+/// modes
+pub trait PowerState: sealed::Sealed {}
+
+/// Power mode which is fixed for the lifetime of the driver
+pub trait FixedPowerMode: PowerState {
+    /// Power mode of the device
+    const MODE: PowerMode;
+}
+
+/// Power mode in which the accelerometer is enabled
+pub trait AccelEnabled: PowerState {}
+
+/// Power mode in which the gyroscope is enabled
+pub trait GyroEnabled: PowerState {}
+
+/// Power mode in which the MREG1, MREG2 and MREG3 banks may be accessed
+///
+/// [`AccelLowPower`] is excluded, as MREG access is unsupported when the
+/// accelerometer is clocked from the wake-up oscillator.
+pub trait MregAccessible: PowerState {}
+
+/// The power mode is not tracked by the driver, and every operation is
+/// available regardless of the power mode of the device
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Dynamic;
+
+impl sealed::Sealed for Dynamic {}
+impl PowerState for Dynamic {}
+impl AccelEnabled for Dynamic {}
+impl GyroEnabled for Dynamic {}
+impl MregAccessible for Dynamic {}
+
+/// Both sensors are disabled
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Sleep;
+
+impl sealed::Sealed for Sleep {}
+impl PowerState for Sleep {}
+
+impl FixedPowerMode for Sleep {
+    const MODE: PowerMode = PowerMode::Sleep;
+}
+
+/// Both sensors are disabled, but the gyroscope drive is kept running
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Standby;
+
+impl sealed::Sealed for Standby {}
+impl PowerState for Standby {}
+impl MregAccessible for Standby {}
+
+impl FixedPowerMode for Standby {
+    const MODE: PowerMode = PowerMode::Standby;
+}
+
+/// The accelerometer is enabled in low-power mode
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AccelLowPower;
+
+impl sealed::Sealed for AccelLowPower {}
+impl PowerState for AccelLowPower {}
+impl AccelEnabled for AccelLowPower {}
+
+impl FixedPowerMode for AccelLowPower {
+    const MODE: PowerMode = PowerMode::AccelLowPower;
+}
+
+/// The accelerometer is enabled in low-noise mode
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AccelLowNoise;
+
+impl sealed::Sealed for AccelLowNoise {}
+impl PowerState for AccelLowNoise {}
+impl AccelEnabled for AccelLowNoise {}
+impl MregAccessible for AccelLowNoise {}
+
+impl FixedPowerMode for AccelLowNoise {
+    const MODE: PowerMode = PowerMode::AccelLowNoise;
+}
+
+/// The gyroscope is enabled in low-noise mode
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GyroLowNoise;
+
+impl sealed::Sealed for GyroLowNoise {}
+impl PowerState for GyroLowNoise {}
+impl GyroEnabled for GyroLowNoise {}
+impl MregAccessible for GyroLowNoise {}
+
+impl FixedPowerMode for GyroLowNoise {
+    const MODE: PowerMode = PowerMode::GyroLowNoise;
+}
+
+/// Both sensors are enabled in low-noise mode
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SixAxisLowNoise;
+
+impl sealed::Sealed for SixAxisLowNoise {}
+impl PowerState for SixAxisLowNoise {}
+impl AccelEnabled for SixAxisLowNoise {}
+impl GyroEnabled for SixAxisLowNoise {}
+impl MregAccessible for SixAxisLowNoise {}
+
+impl FixedPowerMode for SixAxisLowNoise {
+    const MODE: PowerMode = PowerMode::SixAxisLowNoise;
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Set the power mode of the device, encoding it in the type of the driver
+    ///
+    /// Note that the sensors take some time to start up once enabled; see
+    /// [`SensorError::InvalidSample`](crate::SensorError::InvalidSample).
+    /// When entering [`Sleep`] while the gyroscope is running, `delay` is used
+    /// to respect its minimum on-time of 45ms.
+    pub fn into_mode<M, D>(mut self, delay: &mut D) -> Result<Icm42670<DI, M>, Error<E>>
+    where
+        M: FixedPowerMode,
+        D: DelayMs<u8>,
+    {
+        if M::MODE == PowerMode::Sleep {
+            self.sleep(delay)?;
+        } else {
+            self.update_reg(&Bank0::PWR_MGMT0, M::MODE.bits(), PowerMode::BITMASK)?;
+        }
+
+        Ok(self.with_mode())
+    }
+
+    /// Stop tracking the power mode in the type of the driver
+    pub fn into_dynamic(self) -> Icm42670<DI, Dynamic> {
+        self.with_mode()
+    }
+
+    /// Reinterpret the driver as being in another power mode, without
+    /// touching the device
+    fn with_mode<M>(self) -> Icm42670<DI, M> {
+        Icm42670 {
+            interface: self.interface,
+            apex: self.apex,
+            data_endian: self.data_endian,
+            cached: self.cached,
+            pending_interrupts: self.pending_interrupts,
+            interrupt_clear: self.interrupt_clear,
+            calibration: self.calibration,
+            gyro_temp_model: self.gyro_temp_model,
+            variant: self.variant,
+            config: self.config,
+            observer: self.observer,
+            mode: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Observation of register accesses
+//!
+//! A [`RegisterObserver`] installed using
+//! [`Icm42670::set_register_observer`] is notified of every register read and
+//! write made by the driver, which is useful for tracing, conformance testing
+//! and recording golden transaction logs. Accesses to the MREG banks are
+//! reported both as a whole and as the individual accesses to bank 0 which
+//! perform them.
+
+use core::fmt::{self, Debug};
+
+use crate::{mode::PowerState, register::RegisterBank, Icm42670, Interface};
+
+/// Register bank in which a register resides
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Bank {
+    /// User bank 0, which is accessed directly
+    Bank0,
+    /// MREG1, accessed indirectly via bank 0
+    MReg1,
+    /// MREG2, accessed indirectly via bank 0
+    MReg2,
+    /// MREG3, accessed indirectly via bank 0
+    MReg3,
+}
+
+impl From<RegisterBank> for Bank {
+    fn from(bank: RegisterBank) -> Self {
+        match bank {
+            RegisterBank::MReg1 => Bank::MReg1,
+            RegisterBank::MReg2 => Bank::MReg2,
+            RegisterBank::MReg3 => Bank::MReg3,
+        }
+    }
+}
+
+/// Observer of every register access made by the driver
+///
+/// The observer is shared rather than owned by the driver, so any state it
+/// records must be held using interior mutability.
+pub trait RegisterObserver {
+    /// One or more consecutive registers were read, starting at `addr`
+    ///
+    /// Burst reads from `FIFO_DATA` do not advance the address, so in that
+    /// case every value was read from the same register.
+    fn on_read(&self, bank: Bank, addr: u8, values: &[u8]);
+
+    /// A single register was written
+    fn on_write(&self, bank: Bank, addr: u8, value: u8);
+}
+
+impl Debug for dyn RegisterObserver + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RegisterObserver")
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Install an observer to be notified of every register access, or remove
+    /// the installed observer
+    pub fn set_register_observer(&mut self, observer: Option<&'static dyn RegisterObserver>) {
+        self.observer = observer;
+    }
+
+    /// Report a register read to the installed observer and trace it
+    pub(crate) fn observe_read(&self, bank: Bank, addr: u8, values: &[u8]) {
+        crate::trace::read(bank, addr, values);
+        if let Some(observer) = self.observer {
+            observer.on_read(bank, addr, values);
+        }
+    }
+
+    /// Report a register write to the installed observer and trace it
+    pub(crate) fn observe_write(&self, bank: Bank, addr: u8, value: u8) {
+        crate::trace::write(bank, addr, value);
+        if let Some(observer) = self.observer {
+            observer.on_write(bank, addr, value);
+        }
+    }
+}
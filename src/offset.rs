@@ -0,0 +1,268 @@
+//! User offset registers
+//!
+//! The device adds the user offsets to each sample in hardware, allowing
+//! calibrated biases to be removed without any per-sample processing on the
+//! host. Each offset is a 12-bit signed value, split across the `OFFSET_USER*`
+//! registers.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+use micromath::vector::{F32x3, I16x3};
+
+use crate::{
+    error::SensorError,
+    mode::{MregAccessible, PowerState},
+    register::{field, Mreg1, RegisterBank},
+    Error,
+    Icm42670,
+    Interface,
+};
+
+/// Smallest value which can be held by a 12-bit offset
+const OFFSET_MIN: i16 = -2048;
+/// Largest value which can be held by a 12-bit offset
+const OFFSET_MAX: i16 = 2047;
+
+/// Resolution of the accelerometer offsets, in LSB per milli-g
+const ACCEL_LSB_PER_MG: f32 = 2.0;
+
+/// Combine the upper nibble and lower byte of a 12-bit offset, sign-extending
+/// the result
+fn unpack(hi: u8, lo: u8) -> i16 {
+    let raw = ((hi as u16 & 0xF) << 8) | lo as u16;
+
+    ((raw << 4) as i16) >> 4
+}
+
+/// Split a 12-bit offset into its upper nibble and lower byte
+fn pack(offset: i16) -> (u8, u8) {
+    (((offset >> 8) & 0xF) as u8, offset as u8)
+}
+
+/// Convert an accelerometer offset in milli-g to its register value, rounding
+/// to the nearest representable value
+fn mg_to_accel_offset(mg: f32) -> Result<i16, SensorError> {
+    let lsb = mg * ACCEL_LSB_PER_MG;
+    let lsb = if lsb < 0.0 { lsb - 0.5 } else { lsb + 0.5 };
+    let lsb = lsb as i32;
+
+    if (OFFSET_MIN as i32..=OFFSET_MAX as i32).contains(&lsb) {
+        Ok(lsb as i16)
+    } else {
+        Err(SensorError::OffsetOutOfRange)
+    }
+}
+
+/// Verify that each axis of an offset fits within 12 bits
+fn validate(offsets: I16x3) -> Result<(), SensorError> {
+    let range = OFFSET_MIN..=OFFSET_MAX;
+
+    if [offsets.x, offsets.y, offsets.z]
+        .iter()
+        .all(|offset| range.contains(offset))
+    {
+        Ok(())
+    } else {
+        Err(SensorError::OffsetOutOfRange)
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Read the user offsets of the gyroscope
+    ///
+    /// Offsets have a resolution of 1/32 dps per LSB.
+    pub fn gyro_offsets<D>(&mut self, delay: &mut D) -> Result<I16x3, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        // `OFFSET_USER0` through `OFFSET_USER4`
+        let mut bytes = [0u8; 5];
+        self.read_mregs(delay, RegisterBank::MReg1, &Mreg1::OFFSET_USER0, &mut bytes)?;
+
+        let x = unpack(field::GYRO_X_OFFUSER_H.get(bytes[1]), bytes[0]);
+        let y = unpack(field::GYRO_Y_OFFUSER_H.get(bytes[1]), bytes[2]);
+        let z = unpack(field::GYRO_Z_OFFUSER_H.get(bytes[4]), bytes[3]);
+
+        Ok(I16x3::new(x, y, z))
+    }
+
+    /// Set the user offsets of the gyroscope
+    ///
+    /// Offsets have a resolution of 1/32 dps per LSB, and must lie within the
+    /// range of a 12-bit signed value, which is to say ±64 dps.
+    pub fn set_gyro_offsets<D>(&mut self, delay: &mut D, offsets: I16x3) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        validate(offsets)?;
+
+        let (x_hi, x_lo) = pack(offsets.x);
+        let (y_hi, y_lo) = pack(offsets.y);
+        let (z_hi, z_lo) = pack(offsets.z);
+
+        // `OFFSET_USER0` through `OFFSET_USER3`
+        let values = [
+            x_lo,
+            field::GYRO_Y_OFFUSER_H.bits(y_hi) | field::GYRO_X_OFFUSER_H.bits(x_hi),
+            y_lo,
+            z_lo,
+        ];
+        self.write_mregs(delay, RegisterBank::MReg1, &Mreg1::OFFSET_USER0, &values)?;
+
+        // The upper nibble of `OFFSET_USER4` belongs to the accelerometer.
+        self.write_mfield(delay, field::GYRO_Z_OFFUSER_H, z_hi)
+    }
+
+    /// Read the user offsets of the accelerometer
+    ///
+    /// Offsets have a resolution of 0.5 mg per LSB.
+    pub fn accel_offsets<D>(&mut self, delay: &mut D) -> Result<I16x3, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        // `OFFSET_USER4` through `OFFSET_USER8`
+        let mut bytes = [0u8; 5];
+        self.read_mregs(delay, RegisterBank::MReg1, &Mreg1::OFFSET_USER4, &mut bytes)?;
+
+        let x = unpack(field::ACCEL_X_OFFUSER_H.get(bytes[0]), bytes[1]);
+        let y = unpack(field::ACCEL_Y_OFFUSER_H.get(bytes[3]), bytes[2]);
+        let z = unpack(field::ACCEL_Z_OFFUSER_H.get(bytes[3]), bytes[4]);
+
+        Ok(I16x3::new(x, y, z))
+    }
+
+    /// Set the user offsets of the accelerometer
+    ///
+    /// Offsets have a resolution of 0.5 mg per LSB, and must lie within the
+    /// range of a 12-bit signed value, which is to say ±1g.
+    pub fn set_accel_offsets<D>(&mut self, delay: &mut D, offsets: I16x3) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        validate(offsets)?;
+
+        let (x_hi, x_lo) = pack(offsets.x);
+        let (y_hi, y_lo) = pack(offsets.y);
+        let (z_hi, z_lo) = pack(offsets.z);
+
+        // The lower nibble of `OFFSET_USER4` belongs to the gyroscope.
+        self.write_mfield(delay, field::ACCEL_X_OFFUSER_H, x_hi)?;
+
+        // `OFFSET_USER5` through `OFFSET_USER8`
+        let values = [
+            x_lo,
+            y_lo,
+            field::ACCEL_Z_OFFUSER_H.bits(z_hi) | field::ACCEL_Y_OFFUSER_H.bits(y_hi),
+            z_lo,
+        ];
+        self.write_mregs(delay, RegisterBank::MReg1, &Mreg1::OFFSET_USER5, &values)
+    }
+
+    /// Read the user offsets of the accelerometer, in milli-g
+    pub fn accel_offsets_mg<D>(&mut self, delay: &mut D) -> Result<F32x3, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let offsets = self.accel_offsets(delay)?;
+
+        Ok(F32x3::new(
+            offsets.x as f32 / ACCEL_LSB_PER_MG,
+            offsets.y as f32 / ACCEL_LSB_PER_MG,
+            offsets.z as f32 / ACCEL_LSB_PER_MG,
+        ))
+    }
+
+    /// Set the user offsets of the accelerometer, in milli-g
+    ///
+    /// Offsets are rounded to the nearest representable value; the largest
+    /// offset which can be represented is ±1000 mg.
+    pub fn set_accel_offsets_mg<D>(&mut self, delay: &mut D, mg: F32x3) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let offsets = I16x3::new(
+            mg_to_accel_offset(mg.x)?,
+            mg_to_accel_offset(mg.y)?,
+            mg_to_accel_offset(mg.z)?,
+        );
+
+        self.set_accel_offsets(delay, offsets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpack_sign_extends() {
+        assert_eq!(unpack(0x0, 0x00), 0);
+        assert_eq!(unpack(0x0, 0x01), 1);
+        assert_eq!(unpack(0x7, 0xFF), OFFSET_MAX);
+        assert_eq!(unpack(0x8, 0x00), OFFSET_MIN);
+        assert_eq!(unpack(0xF, 0xFF), -1);
+    }
+
+    #[test]
+    fn unpack_ignores_upper_nibble() {
+        // The upper nibble of the register holds another axis' offset.
+        assert_eq!(unpack(0xAF, 0xFF), -1);
+        assert_eq!(unpack(0x50, 0x01), 1);
+    }
+
+    #[test]
+    fn pack_round_trips() {
+        for offset in [OFFSET_MIN, -1000, -1, 0, 1, 1000, OFFSET_MAX] {
+            let (hi, lo) = pack(offset);
+
+            assert!(hi <= 0xF);
+            assert_eq!(unpack(hi, lo), offset);
+        }
+    }
+
+    #[test]
+    fn validate_range() {
+        assert!(validate(I16x3::new(OFFSET_MIN, 0, OFFSET_MAX)).is_ok());
+        assert!(matches!(
+            validate(I16x3::new(0, OFFSET_MAX + 1, 0)),
+            Err(SensorError::OffsetOutOfRange)
+        ));
+        assert!(matches!(
+            validate(I16x3::new(0, 0, OFFSET_MIN - 1)),
+            Err(SensorError::OffsetOutOfRange)
+        ));
+    }
+
+    #[test]
+    fn accel_offset_rounds_to_nearest() {
+        assert_eq!(mg_to_accel_offset(0.0).unwrap(), 0);
+        assert_eq!(mg_to_accel_offset(0.3).unwrap(), 1);
+        assert_eq!(mg_to_accel_offset(-0.3).unwrap(), -1);
+        assert_eq!(mg_to_accel_offset(-1024.0).unwrap(), OFFSET_MIN);
+        assert_eq!(mg_to_accel_offset(1023.5).unwrap(), OFFSET_MAX);
+    }
+
+    #[test]
+    fn accel_offset_out_of_range() {
+        assert!(matches!(
+            mg_to_accel_offset(1024.0),
+            Err(SensorError::OffsetOutOfRange)
+        ));
+        assert!(matches!(
+            mg_to_accel_offset(-1024.5),
+            Err(SensorError::OffsetOutOfRange)
+        ));
+    }
+}
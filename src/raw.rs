@@ -0,0 +1,92 @@
+//! Raw register access, for registers which the typed API does not yet cover
+//!
+//! These methods perform no validation whatsoever, and can easily leave the
+//! device in a state which the driver does not expect; for example, changing
+//! the sensor data byte order behind the driver's back will corrupt any data
+//! subsequently read. After writing the range or output data rate of either
+//! sensor, call [`Icm42670::refresh_config`] so that the driver does not
+//! continue to use the values it has cached. They are only available when the
+//! `advanced` feature is enabled.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    mode::{MregAccessible, PowerState},
+    register::{RawRegister, RegisterBank},
+    Bank,
+    Error,
+    Icm42670,
+    Interface,
+};
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Read the register at the given address in bank 0
+    ///
+    /// Unlike [`Icm42670::read_register`], this is available in every power
+    /// mode.
+    pub fn read_bank0_register(&mut self, addr: u8) -> Result<u8, Error<E>> {
+        self.read_reg(&RawRegister(addr))
+    }
+
+    /// Write a value to the register at the given address in bank 0
+    ///
+    /// Unlike [`Icm42670::write_register`], this is available in every power
+    /// mode. No check is made that the register is writable.
+    pub fn write_bank0_register(&mut self, addr: u8, value: u8) -> Result<(), Error<E>> {
+        self.write_reg(&RawRegister(addr), value)
+    }
+
+    /// Read the register at the given address in the given bank
+    ///
+    /// The delay is only used when accessing the MREG banks. Bank 0 can also be
+    /// accessed in power modes which do not permit MREG access using
+    /// [`Icm42670::read_bank0_register`].
+    pub fn read_register<D>(&mut self, delay: &mut D, bank: Bank, addr: u8) -> Result<u8, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let reg = RawRegister(addr);
+
+        match bank {
+            Bank::Bank0 => self.read_reg(&reg),
+            Bank::MReg1 => self.read_mreg(delay, RegisterBank::MReg1, &reg),
+            Bank::MReg2 => self.read_mreg(delay, RegisterBank::MReg2, &reg),
+            Bank::MReg3 => self.read_mreg(delay, RegisterBank::MReg3, &reg),
+        }
+    }
+
+    /// Write a value to the register at the given address in the given bank
+    ///
+    /// The delay is only used when accessing the MREG banks. No check is made
+    /// that the register is writable. Bank 0 can also be accessed in power
+    /// modes which do not permit MREG access using
+    /// [`Icm42670::write_bank0_register`].
+    pub fn write_register<D>(
+        &mut self,
+        delay: &mut D,
+        bank: Bank,
+        addr: u8,
+        value: u8,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let reg = RawRegister(addr);
+
+        match bank {
+            Bank::Bank0 => self.write_reg(&reg, value),
+            Bank::MReg1 => self.write_mreg(delay, RegisterBank::MReg1, &reg, value),
+            Bank::MReg2 => self.write_mreg(delay, RegisterBank::MReg2, &reg, value),
+            Bank::MReg3 => self.write_mreg(delay, RegisterBank::MReg3, &reg, value),
+        }
+    }
+}
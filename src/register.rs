@@ -97,6 +97,68 @@ pub(crate) enum Bank0 {
     M_R               = 0x7E,
 }
 
+impl Bank0 {
+    /// Registers which can be read without side effects, in order of address
+    ///
+    /// The interrupt status registers are cleared by reading them, `FIFO_DATA`
+    /// pops data from the FIFO, and the registers used to access the MREG
+    /// banks are excluded.
+    pub(crate) const DUMPABLE: [Bank0; 52] = [
+        Bank0::MCLK_RDY,
+        Bank0::DEVICE_CONFIG,
+        Bank0::SIGNAL_PATH_RESET,
+        Bank0::DRIVE_CONFIG1,
+        Bank0::DRIVE_CONFIG2,
+        Bank0::DRIVE_CONFIG3,
+        Bank0::INT_CONFIG,
+        Bank0::TEMP_DATA1,
+        Bank0::TEMP_DATA0,
+        Bank0::ACCEL_DATA_X1,
+        Bank0::ACCEL_DATA_X0,
+        Bank0::ACCEL_DATA_Y1,
+        Bank0::ACCEL_DATA_Y0,
+        Bank0::ACCEL_DATA_Z1,
+        Bank0::ACCEL_DATA_Z0,
+        Bank0::GYRO_DATA_X1,
+        Bank0::GYRO_DATA_X0,
+        Bank0::GYRO_DATA_Y1,
+        Bank0::GYRO_DATA_Y0,
+        Bank0::GYRO_DATA_Z1,
+        Bank0::GYRO_DATA_Z0,
+        Bank0::TMST_FSYNCH,
+        Bank0::TMST_FSYNCL,
+        Bank0::APEX_DATA4,
+        Bank0::APEX_DATA5,
+        Bank0::PWR_MGMT0,
+        Bank0::GYRO_CONFIG0,
+        Bank0::ACCEL_CONFIG0,
+        Bank0::TEMP_CONFIG0,
+        Bank0::GYRO_CONFIG1,
+        Bank0::ACCEL_CONFIG1,
+        Bank0::APEX_CONFIG0,
+        Bank0::APEX_CONFIG1,
+        Bank0::WOM_CONFIG,
+        Bank0::FIFO_CONFIG1,
+        Bank0::FIFO_CONFIG2,
+        Bank0::FIFO_CONFIG3,
+        Bank0::INT_SOURCE0,
+        Bank0::INT_SOURCE1,
+        Bank0::INT_SOURCE3,
+        Bank0::INT_SOURCE4,
+        Bank0::FIFO_LOST_PKT0,
+        Bank0::FIFO_LOST_PKT1,
+        Bank0::APEX_DATA0,
+        Bank0::APEX_DATA1,
+        Bank0::APEX_DATA2,
+        Bank0::APEX_DATA3,
+        Bank0::INTF_CONFIG0,
+        Bank0::INTF_CONFIG1,
+        Bank0::FIFO_COUNTH,
+        Bank0::FIFO_COUNTL,
+        Bank0::WHO_AM_I,
+    ];
+}
+
 impl Register for Bank0 {
     fn addr(&self) -> u8 {
         *self as u8
@@ -152,6 +214,7 @@ pub(crate) enum Mreg1 {
     SENSOR_CONFIG3  = 0x06,
     ST_CONFIG       = 0x13,
     SELFTEST        = 0x14,
+    INTF_CONFIG5    = 0x22,
     INTF_CONFIG6    = 0x23,
     INTF_CONFIG10   = 0x25,
     INTF_CONFIG7    = 0x28,
@@ -186,6 +249,54 @@ pub(crate) enum Mreg1 {
     APEX_CONFIG12   = 0x67,
 }
 
+impl Mreg1 {
+    /// Every register in the bank, in order of address
+    pub(crate) const ALL: [Mreg1; 42] = [
+        Mreg1::TMST_CONFIG1,
+        Mreg1::FIFO_CONFIG5,
+        Mreg1::FIFO_CONFIG6,
+        Mreg1::FSYNC_CONFIG,
+        Mreg1::INT_CONFIG0,
+        Mreg1::INT_CONFIG1,
+        Mreg1::SENSOR_CONFIG3,
+        Mreg1::ST_CONFIG,
+        Mreg1::SELFTEST,
+        Mreg1::INTF_CONFIG5,
+        Mreg1::INTF_CONFIG6,
+        Mreg1::INTF_CONFIG10,
+        Mreg1::INTF_CONFIG7,
+        Mreg1::OTP_CONFIG,
+        Mreg1::INT_SOURCE6,
+        Mreg1::INT_SOURCE7,
+        Mreg1::INT_SOURCE8,
+        Mreg1::INT_SOURCE9,
+        Mreg1::INT_SOURCE10,
+        Mreg1::APEX_CONFIG2,
+        Mreg1::APEX_CONFIG3,
+        Mreg1::APEX_CONFIG4,
+        Mreg1::APEX_CONFIG5,
+        Mreg1::APEX_CONFIG9,
+        Mreg1::APEX_CONFIG10,
+        Mreg1::APEX_CONFIG11,
+        Mreg1::ACCEL_WOM_X_THR,
+        Mreg1::ACCEL_WOM_Y_THR,
+        Mreg1::ACCEL_WOM_Z_THR,
+        Mreg1::OFFSET_USER0,
+        Mreg1::OFFSET_USER1,
+        Mreg1::OFFSET_USER2,
+        Mreg1::OFFSET_USER3,
+        Mreg1::OFFSET_USER4,
+        Mreg1::OFFSET_USER5,
+        Mreg1::OFFSET_USER6,
+        Mreg1::OFFSET_USER7,
+        Mreg1::OFFSET_USER8,
+        Mreg1::ST_STATUS1,
+        Mreg1::ST_STATUS2,
+        Mreg1::FDR_CONFIG,
+        Mreg1::APEX_CONFIG12,
+    ];
+}
+
 impl Register for Mreg1 {
     fn addr(&self) -> u8 {
         *self as u8
@@ -201,6 +312,11 @@ pub(crate) enum Mreg2 {
     OTP_CTRL7 = 0x06,
 }
 
+impl Mreg2 {
+    /// Every register in the bank, in order of address
+    pub(crate) const ALL: [Mreg2; 1] = [Mreg2::OTP_CTRL7];
+}
+
 impl Register for Mreg2 {
     fn addr(&self) -> u8 {
         *self as u8
@@ -221,6 +337,18 @@ pub(crate) enum Mreg3 {
     ZG_ST_DATA = 0x05,
 }
 
+impl Mreg3 {
+    /// Every register in the bank, in order of address
+    pub(crate) const ALL: [Mreg3; 6] = [
+        Mreg3::XA_ST_DATA,
+        Mreg3::YA_ST_DATA,
+        Mreg3::ZA_ST_DATA,
+        Mreg3::XG_ST_DATA,
+        Mreg3::YG_ST_DATA,
+        Mreg3::ZG_ST_DATA,
+    ];
+}
+
 impl Register for Mreg3 {
     fn addr(&self) -> u8 {
         *self as u8
@@ -230,3 +358,265 @@ impl Register for Mreg3 {
         true
     }
 }
+
+/// Register at an arbitrary address, for which no definition exists
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RawRegister(pub u8);
+
+impl Register for RawRegister {
+    fn addr(&self) -> u8 {
+        self.0
+    }
+
+    fn read_only(&self) -> bool {
+        false
+    }
+}
+
+/// Field of one or more contiguous bits within a register
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Field<R> {
+    /// Register in which the field resides
+    pub reg: R,
+    /// Position of the least significant bit of the field
+    pub shift: u8,
+    /// Number of bits occupied by the field
+    pub width: u8,
+}
+
+impl<R> Field<R> {
+    const fn new(reg: R, shift: u8, width: u8) -> Self {
+        Self { reg, shift, width }
+    }
+
+    /// Mask of the bits occupied by the field within the register
+    pub(crate) const fn mask(&self) -> u8 {
+        (((1u16 << self.width) - 1) << self.shift) as u8
+    }
+
+    /// Largest value which the field can hold
+    pub(crate) const fn max(&self) -> u8 {
+        self.mask() >> self.shift
+    }
+
+    /// Extract the value of the field from the value of its register
+    pub(crate) const fn get(&self, bits: u8) -> u8 {
+        (bits & self.mask()) >> self.shift
+    }
+
+    /// Shift a value of the field to its position within the register,
+    /// discarding any bits which do not fit
+    pub(crate) const fn bits(&self, value: u8) -> u8 {
+        (value << self.shift) & self.mask()
+    }
+}
+
+/// Fields of the registers in bank 0 and MREG1, named as in the datasheet
+///
+/// Where several adjacent single-bit fields are always accessed together,
+/// such as the per-axis enable bits, they are grouped into a single field.
+pub(crate) mod field {
+    use super::{Bank0, Field, Mreg1, Mreg2};
+
+    // SIGNAL_PATH_RESET
+    pub(crate) const SOFT_RESET_DEVICE_CONFIG: Field<Bank0> =
+        Field::new(Bank0::SIGNAL_PATH_RESET, 4, 1);
+    pub(crate) const FIFO_FLUSH: Field<Bank0> = Field::new(Bank0::SIGNAL_PATH_RESET, 2, 1);
+
+    // INT_CONFIG, where the `INTx_MODE`, `INTx_DRIVE_CIRCUIT` and
+    // `INTx_POLARITY` fields of each pin are grouped
+    pub(crate) const INT2_CONFIG: Field<Bank0> = Field::new(Bank0::INT_CONFIG, 3, 3);
+    pub(crate) const INT1_CONFIG: Field<Bank0> = Field::new(Bank0::INT_CONFIG, 0, 3);
+    pub(crate) const INT1_MODE: Field<Bank0> = Field::new(Bank0::INT_CONFIG, 2, 1);
+    pub(crate) const INT1_DRIVE_CIRCUIT: Field<Bank0> = Field::new(Bank0::INT_CONFIG, 1, 1);
+    pub(crate) const INT1_POLARITY: Field<Bank0> = Field::new(Bank0::INT_CONFIG, 0, 1);
+
+    // PWR_MGMT0
+    pub(crate) const GYRO_MODE: Field<Bank0> = Field::new(Bank0::PWR_MGMT0, 2, 2);
+    pub(crate) const ACCEL_MODE: Field<Bank0> = Field::new(Bank0::PWR_MGMT0, 0, 2);
+
+    // GYRO_CONFIG0
+    pub(crate) const GYRO_UI_FS_SEL: Field<Bank0> = Field::new(Bank0::GYRO_CONFIG0, 5, 2);
+    pub(crate) const GYRO_ODR: Field<Bank0> = Field::new(Bank0::GYRO_CONFIG0, 0, 4);
+
+    // ACCEL_CONFIG0
+    pub(crate) const ACCEL_UI_FS_SEL: Field<Bank0> = Field::new(Bank0::ACCEL_CONFIG0, 5, 2);
+    pub(crate) const ACCEL_ODR: Field<Bank0> = Field::new(Bank0::ACCEL_CONFIG0, 0, 4);
+
+    // GYRO_CONFIG1
+    pub(crate) const GYRO_UI_FILT_BW: Field<Bank0> = Field::new(Bank0::GYRO_CONFIG1, 0, 3);
+
+    // ACCEL_CONFIG1
+    pub(crate) const ACCEL_UI_FILT_BW: Field<Bank0> = Field::new(Bank0::ACCEL_CONFIG1, 0, 3);
+
+    // APEX_CONFIG0
+    pub(crate) const DMP_POWER_SAVE_EN: Field<Bank0> = Field::new(Bank0::APEX_CONFIG0, 3, 1);
+    pub(crate) const DMP_INIT_EN: Field<Bank0> = Field::new(Bank0::APEX_CONFIG0, 2, 1);
+    pub(crate) const DMP_MEM_RESET_EN: Field<Bank0> = Field::new(Bank0::APEX_CONFIG0, 0, 1);
+
+    // APEX_CONFIG1
+    pub(crate) const SMD_ENABLE: Field<Bank0> = Field::new(Bank0::APEX_CONFIG1, 6, 1);
+    pub(crate) const FF_ENABLE: Field<Bank0> = Field::new(Bank0::APEX_CONFIG1, 5, 1);
+    pub(crate) const TILT_ENABLE: Field<Bank0> = Field::new(Bank0::APEX_CONFIG1, 4, 1);
+    pub(crate) const PED_ENABLE: Field<Bank0> = Field::new(Bank0::APEX_CONFIG1, 3, 1);
+    pub(crate) const DMP_ODR: Field<Bank0> = Field::new(Bank0::APEX_CONFIG1, 0, 2);
+
+    // WOM_CONFIG
+    pub(crate) const WOM_INT_MODE: Field<Bank0> = Field::new(Bank0::WOM_CONFIG, 2, 1);
+    pub(crate) const WOM_MODE: Field<Bank0> = Field::new(Bank0::WOM_CONFIG, 1, 1);
+    pub(crate) const WOM_EN: Field<Bank0> = Field::new(Bank0::WOM_CONFIG, 0, 1);
+
+    // FIFO_CONFIG1
+    pub(crate) const FIFO_MODE: Field<Bank0> = Field::new(Bank0::FIFO_CONFIG1, 1, 1);
+    pub(crate) const FIFO_BYPASS: Field<Bank0> = Field::new(Bank0::FIFO_CONFIG1, 0, 1);
+
+    // FIFO_CONFIG2 and FIFO_CONFIG3
+    pub(crate) const FIFO_WM_L: Field<Bank0> = Field::new(Bank0::FIFO_CONFIG2, 0, 8);
+    pub(crate) const FIFO_WM_H: Field<Bank0> = Field::new(Bank0::FIFO_CONFIG3, 0, 4);
+
+    // INT_SOURCE0, whose layout is mirrored for `INT2` by INT_SOURCE3
+    pub(crate) const ST_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 7, 1);
+    pub(crate) const FSYNC_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 6, 1);
+    pub(crate) const PLL_RDY_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 5, 1);
+    pub(crate) const RESET_DONE_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 4, 1);
+    pub(crate) const DRDY_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 3, 1);
+    pub(crate) const FIFO_THS_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 2, 1);
+    pub(crate) const FIFO_FULL_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 1, 1);
+    pub(crate) const AGC_RDY_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE0, 0, 1);
+
+    // INT_SOURCE1, whose layout is mirrored for `INT2` by INT_SOURCE4
+    pub(crate) const I3C_PROTOCOL_ERROR_INT1_EN: Field<Bank0> =
+        Field::new(Bank0::INT_SOURCE1, 6, 1);
+    pub(crate) const SMD_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE1, 3, 1);
+    pub(crate) const WOM_Z_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE1, 2, 1);
+    pub(crate) const WOM_Y_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE1, 1, 1);
+    pub(crate) const WOM_X_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE1, 0, 1);
+
+    // INT_SOURCE1 and INT_SOURCE4, where `WOM_Z_INTx_EN`, `WOM_Y_INTx_EN` and
+    // `WOM_X_INTx_EN` are grouped
+    pub(crate) const WOM_INT1_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE1, 0, 3);
+    pub(crate) const WOM_INT2_EN: Field<Bank0> = Field::new(Bank0::INT_SOURCE4, 0, 3);
+
+    // APEX_DATA3
+    pub(crate) const DMP_IDLE: Field<Bank0> = Field::new(Bank0::APEX_DATA3, 2, 1);
+    pub(crate) const ACTIVITY_CLASS: Field<Bank0> = Field::new(Bank0::APEX_DATA3, 0, 2);
+
+    // INTF_CONFIG0
+    pub(crate) const FIFO_COUNT_FORMAT: Field<Bank0> = Field::new(Bank0::INTF_CONFIG0, 6, 1);
+    pub(crate) const FIFO_COUNT_ENDIAN: Field<Bank0> = Field::new(Bank0::INTF_CONFIG0, 5, 1);
+    pub(crate) const SENSOR_DATA_ENDIAN: Field<Bank0> = Field::new(Bank0::INTF_CONFIG0, 4, 1);
+    pub(crate) const UI_SIFS_CFG: Field<Bank0> = Field::new(Bank0::INTF_CONFIG0, 0, 2);
+
+    // INTF_CONFIG1
+    pub(crate) const RTC_MODE: Field<Bank0> = Field::new(Bank0::INTF_CONFIG1, 2, 1);
+
+    // INT_STATUS_DRDY
+    pub(crate) const DATA_RDY_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS_DRDY, 0, 1);
+
+    // INT_STATUS
+    pub(crate) const ST_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS, 7, 1);
+    pub(crate) const FSYNC_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS, 6, 1);
+    pub(crate) const PLL_RDY_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS, 5, 1);
+    pub(crate) const RESET_DONE_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS, 4, 1);
+    pub(crate) const FIFO_THS_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS, 2, 1);
+    pub(crate) const FIFO_FULL_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS, 1, 1);
+    pub(crate) const AGC_RDY_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS, 0, 1);
+
+    // INT_STATUS2
+    pub(crate) const SMD_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS2, 3, 1);
+    pub(crate) const WOM_Z_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS2, 2, 1);
+    pub(crate) const WOM_Y_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS2, 1, 1);
+    pub(crate) const WOM_X_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS2, 0, 1);
+
+    // INT_STATUS3
+    pub(crate) const STEP_DET_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS3, 5, 1);
+    pub(crate) const STEP_CNT_OVF_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS3, 4, 1);
+    pub(crate) const TILT_DET_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS3, 3, 1);
+    pub(crate) const FF_DET_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS3, 2, 1);
+    pub(crate) const LOWG_DET_INT: Field<Bank0> = Field::new(Bank0::INT_STATUS3, 1, 1);
+
+    // TMST_CONFIG1
+    pub(crate) const TMST_RES: Field<Mreg1> = Field::new(Mreg1::TMST_CONFIG1, 3, 1);
+    pub(crate) const TMST_DELTA_EN: Field<Mreg1> = Field::new(Mreg1::TMST_CONFIG1, 2, 1);
+    pub(crate) const TMST_FSYNC_EN: Field<Mreg1> = Field::new(Mreg1::TMST_CONFIG1, 1, 1);
+    pub(crate) const TMST_EN: Field<Mreg1> = Field::new(Mreg1::TMST_CONFIG1, 0, 1);
+
+    // FIFO_CONFIG5
+    pub(crate) const FIFO_HIRES_EN: Field<Mreg1> = Field::new(Mreg1::FIFO_CONFIG5, 3, 1);
+    pub(crate) const FIFO_TMST_FSYNC_EN: Field<Mreg1> = Field::new(Mreg1::FIFO_CONFIG5, 2, 1);
+    pub(crate) const FIFO_GYRO_EN: Field<Mreg1> = Field::new(Mreg1::FIFO_CONFIG5, 1, 1);
+    pub(crate) const FIFO_ACCEL_EN: Field<Mreg1> = Field::new(Mreg1::FIFO_CONFIG5, 0, 1);
+
+    // FSYNC_CONFIG
+    pub(crate) const FSYNC_UI_SEL: Field<Mreg1> = Field::new(Mreg1::FSYNC_CONFIG, 4, 3);
+    pub(crate) const FSYNC_POLARITY: Field<Mreg1> = Field::new(Mreg1::FSYNC_CONFIG, 0, 1);
+
+    // INT_CONFIG0
+    pub(crate) const UI_DRDY_INT_CLEAR: Field<Mreg1> = Field::new(Mreg1::INT_CONFIG0, 4, 2);
+    pub(crate) const FIFO_THS_INT_CLEAR: Field<Mreg1> = Field::new(Mreg1::INT_CONFIG0, 2, 2);
+    pub(crate) const FIFO_FULL_INT_CLEAR: Field<Mreg1> = Field::new(Mreg1::INT_CONFIG0, 0, 2);
+
+    // INT_SOURCE6, whose layout is mirrored for `INT2` by INT_SOURCE7
+    pub(crate) const STEP_DET_INT1_EN: Field<Mreg1> = Field::new(Mreg1::INT_SOURCE6, 5, 1);
+    pub(crate) const STEP_CNT_OFL_INT1_EN: Field<Mreg1> = Field::new(Mreg1::INT_SOURCE6, 4, 1);
+    pub(crate) const TILT_DET_INT1_EN: Field<Mreg1> = Field::new(Mreg1::INT_SOURCE6, 3, 1);
+    pub(crate) const FF_DET_INT1_EN: Field<Mreg1> = Field::new(Mreg1::INT_SOURCE6, 2, 1);
+    pub(crate) const LOWG_DET_INT1_EN: Field<Mreg1> = Field::new(Mreg1::INT_SOURCE6, 1, 1);
+
+    // SENSOR_CONFIG3
+    pub(crate) const APEX_DISABLE: Field<Mreg1> = Field::new(Mreg1::SENSOR_CONFIG3, 6, 1);
+
+    // SELFTEST, where `EN_GZ_ST`, `EN_GY_ST` and `EN_GX_ST` are grouped, as are
+    // `EN_AZ_ST`, `EN_AY_ST` and `EN_AX_ST`
+    pub(crate) const GYRO_ST_EN: Field<Mreg1> = Field::new(Mreg1::SELFTEST, 7, 1);
+    pub(crate) const ACCEL_ST_EN: Field<Mreg1> = Field::new(Mreg1::SELFTEST, 6, 1);
+    pub(crate) const EN_G_ST: Field<Mreg1> = Field::new(Mreg1::SELFTEST, 3, 3);
+    pub(crate) const EN_A_ST: Field<Mreg1> = Field::new(Mreg1::SELFTEST, 0, 3);
+
+    // INTF_CONFIG5
+    pub(crate) const PIN9_FUNCTION: Field<Mreg1> = Field::new(Mreg1::INTF_CONFIG5, 1, 2);
+
+    // OTP_CONFIG
+    pub(crate) const OTP_COPY_MODE: Field<Mreg1> = Field::new(Mreg1::OTP_CONFIG, 2, 2);
+
+    // APEX_CONFIG2
+    pub(crate) const DMP_POWER_SAVE_TIME_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG2, 0, 4);
+
+    // APEX_CONFIG5
+    pub(crate) const TILT_WAIT_TIME_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG5, 6, 2);
+    pub(crate) const LOWG_PEAK_TH_HYST_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG5, 3, 3);
+    pub(crate) const HIGHG_PEAK_TH_HYST_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG5, 0, 3);
+
+    // APEX_CONFIG9
+    pub(crate) const SMD_SENSITIVITY_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG9, 1, 3);
+    pub(crate) const SENSITIVITY_MODE: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG9, 0, 1);
+    pub(crate) const FF_DEBOUNCE_DURATION_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG9, 4, 4);
+
+    // APEX_CONFIG10
+    pub(crate) const LOWG_PEAK_TH_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG10, 3, 5);
+    pub(crate) const LOWG_TIME_TH_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG10, 0, 3);
+
+    // APEX_CONFIG11
+    pub(crate) const HIGHG_PEAK_TH_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG11, 3, 5);
+    pub(crate) const HIGHG_TIME_TH_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG11, 0, 3);
+
+    // APEX_CONFIG12
+    pub(crate) const FF_MAX_DURATION_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG12, 4, 4);
+    pub(crate) const FF_MIN_DURATION_SEL: Field<Mreg1> = Field::new(Mreg1::APEX_CONFIG12, 0, 4);
+
+    // OFFSET_USER1
+    pub(crate) const GYRO_Y_OFFUSER_H: Field<Mreg1> = Field::new(Mreg1::OFFSET_USER1, 4, 4);
+    pub(crate) const GYRO_X_OFFUSER_H: Field<Mreg1> = Field::new(Mreg1::OFFSET_USER1, 0, 4);
+
+    // OFFSET_USER4
+    pub(crate) const ACCEL_X_OFFUSER_H: Field<Mreg1> = Field::new(Mreg1::OFFSET_USER4, 4, 4);
+    pub(crate) const GYRO_Z_OFFUSER_H: Field<Mreg1> = Field::new(Mreg1::OFFSET_USER4, 0, 4);
+
+    // OFFSET_USER7
+    pub(crate) const ACCEL_Z_OFFUSER_H: Field<Mreg1> = Field::new(Mreg1::OFFSET_USER7, 4, 4);
+    pub(crate) const ACCEL_Y_OFFUSER_H: Field<Mreg1> = Field::new(Mreg1::OFFSET_USER7, 0, 4);
+
+    // OTP_CTRL7 in MREG2, which is needed to load the self-test data
+    pub(crate) const OTP_RELOAD: Field<Mreg2> = Field::new(Mreg2::OTP_CTRL7, 3, 1);
+    pub(crate) const OTP_PWR_DOWN: Field<Mreg2> = Field::new(Mreg2::OTP_CTRL7, 1, 1);
+}
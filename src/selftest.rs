@@ -0,0 +1,349 @@
+//! Hardware self-test
+//!
+//! When self-test is enabled, the device applies an electrostatic force to
+//! each sensor axis, deflecting it as though it had been subjected to an
+//! acceleration or rotation. The change in output, known as the self-test
+//! response, is compared against the response measured at the factory, which
+//! is stored in OTP memory and read back via the `*_ST_DATA` registers.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::{DelayMs, DelayUs};
+use micromath::vector::F32x3;
+
+use crate::{
+    error::SensorError,
+    mode::{MregAccessible, PowerState},
+    register::{field, Bank0, Mreg1, Mreg3, RegisterBank},
+    AccelOdr,
+    AccelRange,
+    Error,
+    GyroOdr,
+    GyroRange,
+    Icm42670,
+    Interface,
+    PowerMode,
+};
+
+/// Number of samples averaged for each self-test measurement
+const SAMPLES: u16 = 200;
+
+/// Self-test response corresponding to a factory code of 1, in LSB, at
+/// ±2g and ±250 deg/sec
+const RESPONSE_BASE: f32 = 2620.0;
+
+/// Acceptance window for the ratio of each measured self-test response to its
+/// factory response
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestLimits {
+    /// Smallest acceptable ratio for the accelerometer
+    pub accel_min_ratio: f32,
+    /// Largest acceptable ratio for the accelerometer
+    pub accel_max_ratio: f32,
+    /// Smallest acceptable ratio for the gyroscope
+    pub gyro_min_ratio: f32,
+    /// Largest acceptable ratio for the gyroscope
+    pub gyro_max_ratio: f32,
+}
+
+impl Default for SelfTestLimits {
+    fn default() -> Self {
+        // The gyroscope response is only required to meet a minimum.
+        Self {
+            accel_min_ratio: 0.5,
+            accel_max_ratio: 1.5,
+            gyro_min_ratio: 0.5,
+            gyro_max_ratio: f32::MAX,
+        }
+    }
+}
+
+/// Self-test measurements of a single axis
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestAxis {
+    /// Magnitude of the change in output caused by enabling self-test, in LSB
+    /// at ±2g or ±250 deg/sec
+    pub response: f32,
+    /// Self-test response measured at the factory, in LSB, or `None` if no
+    /// factory response was recorded
+    pub factory_response: Option<f32>,
+    /// Ratio of the measured response to the factory response, or `None` if no
+    /// factory response was recorded
+    pub ratio: Option<f32>,
+    /// The ratio lies within the acceptance window
+    pub passed: bool,
+}
+
+/// Self-test measurements of each axis of a sensor
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestAxes {
+    /// Measurements of the X axis
+    pub x: SelfTestAxis,
+    /// Measurements of the Y axis
+    pub y: SelfTestAxis,
+    /// Measurements of the Z axis
+    pub z: SelfTestAxis,
+}
+
+impl SelfTestAxes {
+    /// Every axis passed
+    pub fn passed(&self) -> bool {
+        self.x.passed && self.y.passed && self.z.passed
+    }
+}
+
+/// Self-test report of the accelerometer and gyroscope
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SelfTestResult {
+    /// Measurements of the accelerometer
+    pub accel: SelfTestAxes,
+    /// Measurements of the gyroscope
+    pub gyro: SelfTestAxes,
+}
+
+impl SelfTestResult {
+    /// Every axis of both sensors passed
+    pub fn passed(&self) -> bool {
+        self.accel.passed() && self.gyro.passed()
+    }
+
+    /// Return the report if every axis of both sensors passed, or
+    /// [`SensorError::SelfTestFailed`] otherwise
+    pub fn check(self) -> Result<Self, SensorError> {
+        if self.passed() {
+            Ok(self)
+        } else {
+            Err(SensorError::SelfTestFailed)
+        }
+    }
+}
+
+/// Convert a factory self-test code into the expected self-test response, in
+/// LSB
+///
+/// A code of zero indicates that no factory response was recorded.
+fn factory_response(code: u8) -> Option<f32> {
+    if code == 0 {
+        return None;
+    }
+
+    // response = 2620 * 1.01^(code - 1), computed without `powf` as we are
+    // `no_std`
+    let mut response = RESPONSE_BASE;
+    for _ in 1..code {
+        response *= 1.01;
+    }
+
+    Some(response)
+}
+
+/// Compare the self-test response of each axis against its factory response,
+/// accepting ratios between `min` and `max` inclusive
+fn evaluate(on: F32x3, off: F32x3, codes: [u8; 3], min: f32, max: f32) -> SelfTestAxes {
+    let axis = |on: f32, off: f32, code: u8| {
+        let response = on - off;
+        let response = if response < 0.0 { -response } else { response };
+
+        let factory_response = factory_response(code);
+        let ratio = factory_response.map(|expected| response / expected);
+
+        SelfTestAxis {
+            response,
+            factory_response,
+            ratio,
+            passed: ratio.map_or(false, |ratio| (min..=max).contains(&ratio)),
+        }
+    };
+
+    SelfTestAxes {
+        x: axis(on.x, off.x, codes[0]),
+        y: axis(on.y, off.y, codes[1]),
+        z: axis(on.z, off.z, codes[2]),
+    }
+}
+
+impl<DI, E> Icm42670<DI>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+{
+    /// Run the self-test of both the accelerometer and gyroscope, using the
+    /// default [`SelfTestLimits`]
+    ///
+    /// See [`Icm42670::self_test_with_limits`].
+    pub fn self_test<D>(&mut self, delay: &mut D) -> Result<SelfTestResult, Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+    {
+        self.self_test_with_limits(delay, SelfTestLimits::default())
+    }
+
+    /// Run the self-test of both the accelerometer and gyroscope, accepting
+    /// the responses which lie within the provided limits
+    ///
+    /// The device must remain stationary for the duration of the test, which
+    /// takes roughly one second. Both sensors are temporarily reconfigured
+    /// for the test; their power mode, ranges and output data rates are
+    /// restored and the self-test disabled once it completes, even if an
+    /// error is encountered along the way.
+    pub fn self_test_with_limits<D>(
+        &mut self,
+        delay: &mut D,
+        limits: SelfTestLimits,
+    ) -> Result<SelfTestResult, Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+    {
+        let power_mode = self.power_mode()?;
+        let accel_range = self.accel_range()?;
+        let gyro_range = self.gyro_range()?;
+        let accel_odr = self.accel_odr()?;
+        let gyro_odr = self.gyro_odr()?;
+
+        let result = self.measure_self_test(delay, limits);
+
+        // Leave the device as it was found, whether or not the test itself
+        // succeeded.
+        let restored = self
+            .write_mreg(delay, RegisterBank::MReg1, &Mreg1::SELFTEST, 0)
+            .and_then(|_| {
+                delay.delay_ms(200);
+                self.set_power_mode(power_mode)?;
+                self.set_accel_range(accel_range)?;
+                self.set_gyro_range(gyro_range)?;
+                self.set_accel_odr(accel_odr)?;
+                self.set_gyro_odr(gyro_odr)
+            });
+
+        let result = result?;
+        restored?;
+
+        Ok(result)
+    }
+
+    /// Reconfigure both sensors for the self-test and measure their
+    /// responses, leaving the self-test enabled
+    fn measure_self_test<D>(
+        &mut self,
+        delay: &mut D,
+        limits: SelfTestLimits,
+    ) -> Result<SelfTestResult, Error<E>>
+    where
+        D: DelayMs<u8> + DelayUs<u8>,
+    {
+        // The factory responses were recorded at these ranges, so the
+        // measured responses must be too.
+        self.set_accel_range(AccelRange::G2)?;
+        self.set_gyro_range(GyroRange::Deg250)?;
+        self.set_accel_odr(AccelOdr::Hz800)?;
+        self.set_gyro_odr(GyroOdr::Hz800)?;
+        self.set_power_mode(PowerMode::SixAxisLowNoise)?;
+        delay.delay_ms(100);
+
+        let (accel_codes, gyro_codes) = self.factory_self_test_codes(delay)?;
+
+        let accel_off = self.mean(delay, SAMPLES, 1.0, |imu| {
+            imu.read_reg_vector(&Bank0::ACCEL_DATA_X1)
+        })?;
+        let gyro_off = self.mean(delay, SAMPLES, 1.0, Self::gyro_raw)?;
+
+        // Enable the self-test of both sensors on every axis
+        let enable = field::GYRO_ST_EN.mask()
+            | field::ACCEL_ST_EN.mask()
+            | field::EN_G_ST.mask()
+            | field::EN_A_ST.mask();
+        self.write_mreg(delay, RegisterBank::MReg1, &Mreg1::SELFTEST, enable)?;
+        delay.delay_ms(200);
+
+        let accel_on = self.mean(delay, SAMPLES, 1.0, |imu| {
+            imu.read_reg_vector(&Bank0::ACCEL_DATA_X1)
+        })?;
+        let gyro_on = self.mean(delay, SAMPLES, 1.0, Self::gyro_raw)?;
+
+        Ok(SelfTestResult {
+            accel: evaluate(
+                accel_on,
+                accel_off,
+                accel_codes,
+                limits.accel_min_ratio,
+                limits.accel_max_ratio,
+            ),
+            gyro: evaluate(
+                gyro_on,
+                gyro_off,
+                gyro_codes,
+                limits.gyro_min_ratio,
+                limits.gyro_max_ratio,
+            ),
+        })
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Reload the factory self-test codes from OTP memory and read them back,
+    /// returning the accelerometer and gyroscope codes of each axis
+    /// respectively
+    ///
+    /// The codes are held in the `*_ST_DATA` registers of MREG3, which are
+    /// only populated once the OTP memory has been reloaded via MREG1 and
+    /// MREG2. A code of `n` corresponds to a self-test response of
+    /// `2620 * 1.01^(n - 1)` LSB at ±2g or ±250 deg/sec, and a code of zero
+    /// indicates that no response was recorded at the factory.
+    pub fn factory_self_test_codes<D>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<([u8; 3], [u8; 3]), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        // An `OTP_COPY_MODE` of 0b11 selects the self-test data
+        self.write_mfield(delay, field::OTP_COPY_MODE, 0b11)?;
+
+        let pwr_down = field::OTP_PWR_DOWN;
+        let reload = field::OTP_RELOAD;
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg2,
+            &pwr_down.reg,
+            pwr_down.bits(0),
+            pwr_down.mask(),
+        )?;
+        delay.delay_us(100);
+
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg2,
+            &reload.reg,
+            reload.bits(1),
+            reload.mask(),
+        )?;
+        delay.delay_us(20);
+
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg2,
+            &pwr_down.reg,
+            pwr_down.bits(1),
+            pwr_down.mask(),
+        )?;
+
+        // `XA_ST_DATA` through `ZG_ST_DATA`
+        let mut codes = [0u8; 6];
+        self.read_mregs(delay, RegisterBank::MReg3, &Mreg3::XA_ST_DATA, &mut codes)?;
+
+        Ok((
+            [codes[0], codes[1], codes[2]],
+            [codes[3], codes[4], codes[5]],
+        ))
+    }
+}
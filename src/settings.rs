@@ -0,0 +1,702 @@
+//! Device-wide configuration
+//!
+//! A [`Config`] describes the desired state of the device as a whole, allowing
+//! it to be brought up in a single call to [`Icm42670::configure`] (or one of
+//! the `*_with_config` constructors) rather than via many individual setters.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+#[cfg(feature = "apex")]
+use crate::DmpOdr;
+use crate::{
+    config::{Bitfield, CachedConfig},
+    error::SensorError,
+    register::{field, Bank0, Register},
+    AccelLpf,
+    AccelOdr,
+    AccelRange,
+    Error,
+    GyroLpf,
+    GyroOdr,
+    GyroRange,
+    Icm42670,
+    IntPinConfig,
+    Interface,
+    InterruptDrive,
+    InterruptMode,
+    InterruptPin,
+    InterruptPolarity,
+    InterruptSource,
+    PowerMode,
+};
+#[cfg(feature = "fifo")]
+use crate::{
+    config::{FifoBypass, FifoMode},
+    fifo::{FifoContent, FIFO_SIZE},
+    register::{Mreg1, RegisterBank},
+    FifoConfig,
+};
+
+/// Interrupt sources routed to each interrupt pin
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterruptRouting {
+    /// Sources routed to `INT1`, one bit per [`InterruptSource`]
+    int1: u16,
+    /// Sources routed to `INT2`, one bit per [`InterruptSource`]
+    int2: u16,
+}
+
+impl InterruptRouting {
+    /// Create a new routing with no sources routed to either pin
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route the given interrupt source to an interrupt pin
+    pub fn route(mut self, source: InterruptSource, pin: InterruptPin) -> Self {
+        *self.pin_mut(pin) |= 1 << source as u16;
+        self
+    }
+
+    /// Is the given interrupt source routed to the interrupt pin?
+    pub fn is_routed(&self, source: InterruptSource, pin: InterruptPin) -> bool {
+        let sources = match pin {
+            InterruptPin::Int1 => self.int1,
+            InterruptPin::Int2 => self.int2,
+        };
+
+        sources & (1 << source as u16) != 0
+    }
+
+    fn pin_mut(&mut self, pin: InterruptPin) -> &mut u16 {
+        match pin {
+            InterruptPin::Int1 => &mut self.int1,
+            InterruptPin::Int2 => &mut self.int2,
+        }
+    }
+}
+
+/// Desired configuration of the device
+///
+/// Every field is applied by [`Icm42670::configure`]. The default
+/// configuration matches the state in which [`Icm42670::new`] leaves the
+/// device, with the FIFO bypassed and no interrupts routed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Config {
+    /// Power mode of the device
+    pub power_mode: PowerMode,
+    /// Range of the accelerometer
+    pub accel_range: AccelRange,
+    /// Range of the gyroscope
+    pub gyro_range: GyroRange,
+    /// Output data rate of the accelerometer
+    pub accel_odr: AccelOdr,
+    /// Output data rate of the gyroscope
+    pub gyro_odr: GyroOdr,
+    /// Low pass filter bandwidth of the accelerometer
+    pub accel_lpf: AccelLpf,
+    /// Low pass filter bandwidth of the gyroscope
+    pub gyro_lpf: GyroLpf,
+    /// Output data rate of the DMP, or `None` to leave it unchanged
+    #[cfg(feature = "apex")]
+    pub dmp_odr: Option<DmpOdr>,
+    /// Configuration of the FIFO, or `None` to bypass it
+    #[cfg(feature = "fifo")]
+    pub fifo: Option<FifoConfig>,
+    /// FIFO watermark, or `None` to leave it unchanged
+    #[cfg(feature = "fifo")]
+    pub fifo_watermark: Option<u16>,
+    /// Electrical configuration of `INT1`
+    pub int1: IntPinConfig,
+    /// Electrical configuration of `INT2`
+    pub int2: IntPinConfig,
+    /// Interrupt sources routed to each interrupt pin
+    pub interrupts: InterruptRouting,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            power_mode: PowerMode::SixAxisLowNoise,
+            accel_range: AccelRange::default(),
+            gyro_range: GyroRange::default(),
+            accel_odr: AccelOdr::default(),
+            gyro_odr: GyroOdr::default(),
+            accel_lpf: AccelLpf::default(),
+            gyro_lpf: GyroLpf::default(),
+            #[cfg(feature = "apex")]
+            dmp_odr: None,
+            #[cfg(feature = "fifo")]
+            fifo: None,
+            #[cfg(feature = "fifo")]
+            fifo_watermark: None,
+            int1: IntPinConfig::default(),
+            int2: IntPinConfig::default(),
+            interrupts: InterruptRouting::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Push-pull, active high, pulsed interrupt pin, which requires no external
+    /// pull-up and suits most microcontrollers
+    const PUSH_PULL_PIN: IntPinConfig = IntPinConfig {
+        mode: InterruptMode::Pulsed,
+        drive: InterruptDrive::PushPull,
+        polarity: InterruptPolarity::ActiveHigh,
+    };
+
+    /// Low-power preset for battery powered wearables
+    ///
+    /// Only the accelerometer is enabled, in low-power mode at 50 Hz and ±4g,
+    /// which is also the rate at which the pedometer, tilt detection and SMD
+    /// are designed to operate. Samples are batched in the FIFO, raising the
+    /// FIFO threshold interrupt on `INT1` roughly once per second so the host
+    /// can remain asleep in between.
+    ///
+    /// Requires both the `apex` and `fifo` features.
+    #[cfg(all(feature = "apex", feature = "fifo"))]
+    pub fn low_power_wearable() -> Self {
+        Self::default()
+            .with_power_mode(PowerMode::AccelLowPower)
+            .with_accel_range(AccelRange::G4)
+            .with_accel_odr(AccelOdr::Hz50)
+            .with_accel_lpf(AccelLpf::Hz25)
+            .with_dmp_odr(DmpOdr::Hz50)
+            .with_fifo(FifoConfig {
+                mode: FifoMode::Stream,
+                content: FifoContent {
+                    accel: true,
+                    ..FifoContent::default()
+                },
+            })
+            // 50 accelerometer-only packets of 8 bytes each
+            .with_fifo_watermark(50 * 8)
+            .with_interrupt_pin(InterruptPin::Int1, Self::PUSH_PULL_PIN)
+            .with_interrupt(InterruptSource::FifoThreshold, InterruptPin::Int1)
+    }
+
+    /// High-rate, low-latency preset for flight controllers
+    ///
+    /// Both sensors run in low-noise mode at 1.6 kHz with their full ranges,
+    /// so that aggressive manoeuvres do not saturate them. The gyroscope
+    /// filter is bypassed to minimise latency, leaving filtering to the
+    /// control loop, and the data ready interrupt on `INT1` paces the loop.
+    /// The FIFO is bypassed, as each sample is read as soon as it is ready.
+    pub fn high_rate_flight_controller() -> Self {
+        Self::default()
+            .with_power_mode(PowerMode::SixAxisLowNoise)
+            .with_accel_range(AccelRange::G16)
+            .with_gyro_range(GyroRange::Deg2000)
+            .with_accel_odr(AccelOdr::Hz1600)
+            .with_gyro_odr(GyroOdr::Hz1600)
+            .with_accel_lpf(AccelLpf::Hz73)
+            .with_gyro_lpf(GyroLpf::Bypass)
+            .with_interrupt_pin(InterruptPin::Int1, Self::PUSH_PULL_PIN)
+            .with_interrupt(InterruptSource::DataReady, InterruptPin::Int1)
+    }
+
+    /// Preset for logging both sensors at 100 Hz
+    ///
+    /// Both sensors run in low-noise mode at 100 Hz, with ±8g and ±1000 deg/sec
+    /// ranges and filters at roughly a third of the output data rate. Samples
+    /// are written to the FIFO along with their timestamps, and the FIFO stops
+    /// accepting data once full rather than silently discarding the oldest
+    /// samples, so that any gap in the log is detectable. The FIFO threshold
+    /// interrupt on `INT1` is raised every half second.
+    ///
+    /// Requires the `fifo` feature.
+    #[cfg(feature = "fifo")]
+    pub fn datalogger_100hz() -> Self {
+        Self::default()
+            .with_power_mode(PowerMode::SixAxisLowNoise)
+            .with_accel_range(AccelRange::G8)
+            .with_gyro_range(GyroRange::Deg1000)
+            .with_accel_odr(AccelOdr::Hz100)
+            .with_gyro_odr(GyroOdr::Hz100)
+            .with_accel_lpf(AccelLpf::Hz34)
+            .with_gyro_lpf(GyroLpf::Hz34)
+            .with_fifo(FifoConfig {
+                mode: FifoMode::StopOnFull,
+                content: FifoContent {
+                    accel: true,
+                    gyro: true,
+                    timestamp_fsync: true,
+                    high_resolution: false,
+                },
+            })
+            // 50 combined packets of 16 bytes each
+            .with_fifo_watermark(50 * 16)
+            .with_interrupt_pin(InterruptPin::Int1, Self::PUSH_PULL_PIN)
+            .with_interrupt(InterruptSource::FifoThreshold, InterruptPin::Int1)
+    }
+
+    /// Set the power mode of the device
+    pub fn with_power_mode(mut self, mode: PowerMode) -> Self {
+        self.power_mode = mode;
+        self
+    }
+
+    /// Set the range of the accelerometer
+    pub fn with_accel_range(mut self, range: AccelRange) -> Self {
+        self.accel_range = range;
+        self
+    }
+
+    /// Set the range of the gyroscope
+    pub fn with_gyro_range(mut self, range: GyroRange) -> Self {
+        self.gyro_range = range;
+        self
+    }
+
+    /// Set the output data rate of the accelerometer
+    pub fn with_accel_odr(mut self, odr: AccelOdr) -> Self {
+        self.accel_odr = odr;
+        self
+    }
+
+    /// Set the output data rate of the gyroscope
+    pub fn with_gyro_odr(mut self, odr: GyroOdr) -> Self {
+        self.gyro_odr = odr;
+        self
+    }
+
+    /// Set the low pass filter bandwidth of the accelerometer
+    pub fn with_accel_lpf(mut self, lpf: AccelLpf) -> Self {
+        self.accel_lpf = lpf;
+        self
+    }
+
+    /// Set the low pass filter bandwidth of the gyroscope
+    pub fn with_gyro_lpf(mut self, lpf: GyroLpf) -> Self {
+        self.gyro_lpf = lpf;
+        self
+    }
+
+    /// Set the output data rate of the DMP
+    #[cfg(feature = "apex")]
+    pub fn with_dmp_odr(mut self, odr: DmpOdr) -> Self {
+        self.dmp_odr = Some(odr);
+        self
+    }
+
+    /// Enable the FIFO using the given configuration
+    #[cfg(feature = "fifo")]
+    pub fn with_fifo(mut self, fifo: FifoConfig) -> Self {
+        self.fifo = Some(fifo);
+        self
+    }
+
+    /// Set the FIFO watermark
+    #[cfg(feature = "fifo")]
+    pub fn with_fifo_watermark(mut self, watermark: u16) -> Self {
+        self.fifo_watermark = Some(watermark);
+        self
+    }
+
+    /// Set the electrical configuration of an interrupt pin
+    pub fn with_interrupt_pin(mut self, pin: InterruptPin, config: IntPinConfig) -> Self {
+        match pin {
+            InterruptPin::Int1 => self.int1 = config,
+            InterruptPin::Int2 => self.int2 = config,
+        }
+        self
+    }
+
+    /// Route an interrupt source to an interrupt pin
+    pub fn with_interrupt(mut self, source: InterruptSource, pin: InterruptPin) -> Self {
+        self.interrupts = self.interrupts.route(source, pin);
+        self
+    }
+
+    /// Verify that the fields of the configuration are consistent with one
+    /// another
+    ///
+    /// Returns:
+    ///
+    /// - [`SensorError::OdrUnsupportedInPowerMode`] if the accelerometer output
+    ///   data rate is unavailable in the configured power mode
+    /// - [`SensorError::DmpOdrMismatch`] if the DMP output data rate is faster
+    ///   than that of the accelerometer
+    /// - [`SensorError::InvalidWatermark`] if the FIFO watermark is zero or
+    ///   exceeds the size of the FIFO in bytes
+    pub fn validate(&self) -> Result<(), SensorError> {
+        if !self.accel_odr.supported_in(self.power_mode) {
+            return Err(SensorError::OdrUnsupportedInPowerMode);
+        }
+
+        #[cfg(feature = "apex")]
+        if let Some(dmp_odr) = self.dmp_odr {
+            if self.accel_odr.as_f32() < dmp_odr.as_f32() {
+                return Err(SensorError::DmpOdrMismatch);
+            }
+        }
+
+        #[cfg(feature = "fifo")]
+        if let Some(watermark) = self.fifo_watermark {
+            if watermark == 0 || watermark > FIFO_SIZE {
+                return Err(SensorError::InvalidWatermark);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<DI, E> Icm42670<DI>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+{
+    /// Apply every field of the provided configuration to the device
+    ///
+    /// The configuration is validated using [`Config::validate`] before any
+    /// registers are written. The power mode is applied last, so that the
+    /// sensors start up with the desired ranges, output data rates and
+    /// filters. As the FIFO content resides in MREG1, the device must not
+    /// be in [`PowerMode::Sleep`] when this is called.
+    #[cfg_attr(not(feature = "fifo"), allow(unused_variables))]
+    pub fn configure<D>(&mut self, delay: &mut D, config: &Config) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        config.validate()?;
+
+        self.set_accel_range(config.accel_range)?;
+        self.set_gyro_range(config.gyro_range)?;
+        self.set_accel_odr(config.accel_odr)?;
+        self.set_gyro_odr(config.gyro_odr)?;
+        self.set_accel_lpf(config.accel_lpf)?;
+        self.set_gyro_lpf(config.gyro_lpf)?;
+        #[cfg(feature = "apex")]
+        if let Some(odr) = config.dmp_odr {
+            self.set_dmp_odr(odr)?;
+        }
+
+        #[cfg(feature = "fifo")]
+        {
+            match config.fifo {
+                Some(fifo) => {
+                    self.configure_fifo(delay, fifo)?;
+                    self.enable_fifo()?;
+                }
+                None => self.disable_fifo()?,
+            }
+            if let Some(watermark) = config.fifo_watermark {
+                self.set_fifo_watermark(watermark)?;
+            }
+        }
+
+        self.configure_interrupt_pin(InterruptPin::Int1, config.int1)?;
+        self.configure_interrupt_pin(InterruptPin::Int2, config.int2)?;
+        for source in InterruptSource::ALL {
+            for pin in [InterruptPin::Int1, InterruptPin::Int2] {
+                if config.interrupts.is_routed(source, pin) {
+                    self.enable_interrupt(source, pin)?;
+                } else {
+                    self.disable_interrupt(source, pin)?;
+                }
+            }
+        }
+
+        self.set_power_mode(config.power_mode)?;
+        self.config = Some(*config);
+
+        Ok(())
+    }
+
+    /// Apply the provided configuration to the device, writing only those
+    /// registers whose values differ from the desired configuration
+    ///
+    /// Each affected register is read and compared against its desired value,
+    /// so registers which are already correct are never written. This avoids
+    /// restarting the sensors when neither their power mode nor their
+    /// configuration change. The configuration is validated, and the power
+    /// mode applied, as by [`Icm42670::configure`].
+    #[cfg_attr(not(feature = "fifo"), allow(unused_variables))]
+    pub fn apply_config<D>(&mut self, delay: &mut D, config: &Config) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        config.validate()?;
+
+        let sensor_regs = [
+            (
+                Bank0::ACCEL_CONFIG0,
+                config.accel_range.bits() | config.accel_odr.bits(),
+                AccelRange::BITMASK | AccelOdr::BITMASK,
+            ),
+            (
+                Bank0::GYRO_CONFIG0,
+                config.gyro_range.bits() | config.gyro_odr.bits(),
+                GyroRange::BITMASK | GyroOdr::BITMASK,
+            ),
+            (
+                Bank0::ACCEL_CONFIG1,
+                config.accel_lpf.bits(),
+                AccelLpf::BITMASK,
+            ),
+            (
+                Bank0::GYRO_CONFIG1,
+                config.gyro_lpf.bits(),
+                GyroLpf::BITMASK,
+            ),
+        ];
+        self.cached = CachedConfig::default();
+        for (reg, value, mask) in sensor_regs {
+            self.update_reg_if_changed(&reg, value, mask)?;
+        }
+        self.cached = CachedConfig {
+            accel_range: Some(config.accel_range),
+            gyro_range: Some(config.gyro_range),
+            accel_odr: Some(config.accel_odr),
+            gyro_odr: Some(config.gyro_odr),
+        };
+
+        #[cfg(feature = "apex")]
+        if let Some(odr) = config.dmp_odr {
+            self.update_reg_if_changed(&Bank0::APEX_CONFIG1, odr.bits(), DmpOdr::BITMASK)?;
+        }
+
+        #[cfg(feature = "fifo")]
+        {
+            // The FIFO content resides in MREG1, so is only compared when the FIFO
+            // is to be enabled.
+            let (value, mask) = match config.fifo {
+                Some(fifo) => {
+                    let current =
+                        self.read_mreg(delay, RegisterBank::MReg1, &Mreg1::FIFO_CONFIG5)?;
+                    let content = (current & !FifoContent::BITMASK) | fifo.content.bits();
+                    if content != current {
+                        self.write_mreg(delay, RegisterBank::MReg1, &Mreg1::FIFO_CONFIG5, content)?;
+                    }
+
+                    (
+                        FifoBypass::Enabled.bits() | fifo.mode.bits(),
+                        FifoBypass::BITMASK | FifoMode::BITMASK,
+                    )
+                }
+                None => (FifoBypass::Bypassed.bits(), FifoBypass::BITMASK),
+            };
+            self.update_reg_if_changed(&Bank0::FIFO_CONFIG1, value, mask)?;
+
+            if let Some(watermark) = config.fifo_watermark {
+                let [hi, lo] = watermark.to_be_bytes();
+                self.update_reg_if_changed(&Bank0::FIFO_CONFIG2, lo, field::FIFO_WM_L.mask())?;
+                self.update_reg_if_changed(&Bank0::FIFO_CONFIG3, hi, field::FIFO_WM_H.mask())?;
+            }
+        }
+
+        self.update_reg_if_changed(
+            &Bank0::INT_CONFIG,
+            field::INT2_CONFIG.bits(config.int2.bits())
+                | field::INT1_CONFIG.bits(config.int1.bits()),
+            field::INT2_CONFIG.mask() | field::INT1_CONFIG.mask(),
+        )?;
+
+        // Accumulate the enable bits of every interrupt source, so that each of the
+        // interrupt source registers is compared only once.
+        let mut int_sources = [
+            (Bank0::INT_SOURCE0, 0u8, 0u8),
+            (Bank0::INT_SOURCE1, 0u8, 0u8),
+            (Bank0::INT_SOURCE3, 0u8, 0u8),
+            (Bank0::INT_SOURCE4, 0u8, 0u8),
+        ];
+        for source in InterruptSource::ALL {
+            for pin in [InterruptPin::Int1, InterruptPin::Int2] {
+                let enable = source.enable_field(pin);
+                if let Some((_, value, mask)) = int_sources
+                    .iter_mut()
+                    .find(|(r, ..)| r.addr() == enable.reg.addr())
+                {
+                    *mask |= enable.mask();
+                    if config.interrupts.is_routed(source, pin) {
+                        *value |= enable.mask();
+                    }
+                }
+            }
+        }
+        for (reg, value, mask) in int_sources {
+            self.update_reg_if_changed(&reg, value, mask)?;
+        }
+
+        self.update_reg_if_changed(
+            &Bank0::PWR_MGMT0,
+            config.power_mode.bits(),
+            PowerMode::BITMASK,
+        )?;
+        self.config = Some(*config);
+
+        Ok(())
+    }
+
+    /// Read back the complete configuration of the device
+    ///
+    /// Together with [`Icm42670::restore_config`] this allows the device to be
+    /// temporarily reconfigured, for example to run a self-test, and then
+    /// returned to its previous state. A FIFO watermark of zero, as found
+    /// after reset, is reported as `None`, as is the DMP output data rate
+    /// while none of the APEX features which use it are enabled.
+    #[cfg_attr(not(feature = "fifo"), allow(unused_variables))]
+    pub fn read_config<D>(&mut self, delay: &mut D) -> Result<Config, Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        #[cfg(feature = "fifo")]
+        let fifo = match FifoBypass::try_from(self.read_field(field::FIFO_BYPASS)?)? {
+            FifoBypass::Enabled => Some(FifoConfig {
+                mode: self.fifo_mode()?,
+                content: self.fifo_content(delay)?,
+            }),
+            FifoBypass::Bypassed => None,
+        };
+        // The DMP output data rate reads as 50 Hz after reset whether or not APEX
+        // is in use, which would fail validation on restore alongside any slower
+        // accelerometer output data rate, so it is only reported while in use.
+        #[cfg(feature = "apex")]
+        let dmp_odr = {
+            let apex = self.read_reg(&Bank0::APEX_CONFIG1)?;
+            let in_use = [
+                field::PED_ENABLE,
+                field::TILT_ENABLE,
+                field::SMD_ENABLE,
+                field::FF_ENABLE,
+            ]
+            .iter()
+            .any(|enable| enable.get(apex) != 0);
+
+            if in_use {
+                Some(DmpOdr::try_from(field::DMP_ODR.get(apex))?)
+            } else {
+                None
+            }
+        };
+        #[cfg(feature = "fifo")]
+        let fifo_watermark = match self.fifo_watermark()? {
+            0 => None,
+            watermark => Some(watermark),
+        };
+
+        let mut interrupts = InterruptRouting::new();
+        for source in InterruptSource::ALL {
+            for pin in [InterruptPin::Int1, InterruptPin::Int2] {
+                if self.interrupt_enabled(source, pin)? {
+                    interrupts = interrupts.route(source, pin);
+                }
+            }
+        }
+
+        Ok(Config {
+            power_mode: self.power_mode()?,
+            accel_range: self.accel_range()?,
+            gyro_range: self.gyro_range()?,
+            accel_odr: self.accel_odr()?,
+            gyro_odr: self.gyro_odr()?,
+            accel_lpf: self.accel_lpf()?,
+            gyro_lpf: self.gyro_lpf()?,
+            #[cfg(feature = "apex")]
+            dmp_odr,
+            #[cfg(feature = "fifo")]
+            fifo,
+            #[cfg(feature = "fifo")]
+            fifo_watermark,
+            int1: self.interrupt_pin_config(InterruptPin::Int1)?,
+            int2: self.interrupt_pin_config(InterruptPin::Int2)?,
+            interrupts,
+        })
+    }
+
+    /// Restore a configuration previously read with
+    /// [`Icm42670::read_config`]
+    ///
+    /// Only the registers which have changed since the configuration was read
+    /// are written; see [`Icm42670::apply_config`]. As the configuration is
+    /// validated first, a snapshot of an inconsistent device state, such as a
+    /// DMP output data rate faster than that of the accelerometer, cannot be
+    /// restored.
+    pub fn restore_config<D>(&mut self, delay: &mut D, config: &Config) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        self.apply_config(delay, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid() {
+        assert!(Config::default().validate().is_ok());
+        assert!(Config::high_rate_flight_controller().validate().is_ok());
+
+        #[cfg(all(feature = "apex", feature = "fifo"))]
+        assert!(Config::low_power_wearable().validate().is_ok());
+        #[cfg(feature = "fifo")]
+        assert!(Config::datalogger_100hz().validate().is_ok());
+    }
+
+    #[test]
+    fn odr_unsupported_in_power_mode() {
+        let low_noise = Config::default()
+            .with_power_mode(PowerMode::SixAxisLowNoise)
+            .with_accel_odr(AccelOdr::Hz6_25);
+        let low_power = Config::default()
+            .with_power_mode(PowerMode::AccelLowPower)
+            .with_accel_odr(AccelOdr::Hz1600);
+
+        for config in [low_noise, low_power] {
+            assert!(matches!(
+                config.validate(),
+                Err(SensorError::OdrUnsupportedInPowerMode)
+            ));
+        }
+
+        let config = Config::default()
+            .with_power_mode(PowerMode::AccelLowPower)
+            .with_accel_odr(AccelOdr::Hz6_25);
+        assert!(config.validate().is_ok());
+    }
+
+    #[cfg(feature = "apex")]
+    #[test]
+    fn dmp_odr_faster_than_accel() {
+        let config = Config::default()
+            .with_accel_odr(AccelOdr::Hz50)
+            .with_dmp_odr(DmpOdr::Hz100);
+        assert!(matches!(
+            config.validate(),
+            Err(SensorError::DmpOdrMismatch)
+        ));
+
+        let config = config.with_dmp_odr(DmpOdr::Hz50);
+        assert!(config.validate().is_ok());
+    }
+
+    #[cfg(feature = "fifo")]
+    #[test]
+    fn watermark_out_of_range() {
+        for watermark in [0, FIFO_SIZE + 1] {
+            let config = Config::default().with_fifo_watermark(watermark);
+
+            assert!(matches!(
+                config.validate(),
+                Err(SensorError::InvalidWatermark)
+            ));
+        }
+
+        for watermark in [1, FIFO_SIZE] {
+            assert!(Config::default()
+                .with_fifo_watermark(watermark)
+                .validate()
+                .is_ok());
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Persistence of calibration data using [`embedded-storage`]
+//!
+//! [`embedded-storage`]: https://docs.rs/embedded-storage/latest/embedded_storage/
+
+use core::fmt::{self, Debug, Display};
+
+use embedded_storage::{ReadStorage, Storage};
+
+use crate::{Calibration, SensorError};
+
+/// Any type of error which may occur while persisting a calibration
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StorageError<E> {
+    /// Some error originating from the storage device
+    Storage(E),
+    /// The stored data is not a valid calibration
+    Calibration(SensorError),
+}
+
+impl<E> From<SensorError> for StorageError<E> {
+    fn from(err: SensorError) -> Self {
+        StorageError::Calibration(err)
+    }
+}
+
+impl<E> Display for StorageError<E>
+where
+    E: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Storage(e) => write!(f, "storage error: {:?}", e),
+            StorageError::Calibration(e) => Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "core-error"))]
+impl<E> crate::error::StdError for StorageError<E> where E: Debug {}
+
+impl Calibration {
+    /// Write the calibration to storage, starting at the given offset
+    ///
+    /// [`Calibration::SERIALIZED_LEN`] bytes are written.
+    pub fn save<S>(&self, storage: &mut S, offset: u32) -> Result<(), StorageError<S::Error>>
+    where
+        S: Storage,
+    {
+        storage
+            .write(offset, &self.to_bytes())
+            .map_err(StorageError::Storage)
+    }
+
+    /// Read a calibration previously written to storage with
+    /// [`Calibration::save`], starting at the given offset
+    pub fn load<S>(storage: &mut S, offset: u32) -> Result<Self, StorageError<S::Error>>
+    where
+        S: ReadStorage,
+    {
+        let mut bytes = [0u8; Calibration::SERIALIZED_LEN];
+        storage
+            .read(offset, &mut bytes)
+            .map_err(StorageError::Storage)?;
+
+        let calibration = Calibration::from_bytes(&bytes)?;
+
+        Ok(calibration)
+    }
+}
@@ -0,0 +1,193 @@
+//! Correlation of device time with a host clock
+//!
+//! Each device counts time using its own oscillator, which drifts relative to
+//! the host's clock and to the oscillators of any other devices. Observing the
+//! device time alongside the host time allows device timestamps to be mapped
+//! onto the host's timeline, so that samples from several devices can be
+//! compared directly.
+
+/// Linear mapping from device time to host time, in microseconds
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ClockMapping {
+    /// Device time of the reference observation
+    device_origin: u64,
+    /// Host time of the reference observation
+    host_origin: u64,
+    /// Device time elapsed over the observation window
+    device_span: u64,
+    /// Host time elapsed over the observation window
+    host_span: u64,
+}
+
+impl ClockMapping {
+    /// Map a device time onto the host's timeline
+    pub fn to_host(self, device_us: u64) -> u64 {
+        let elapsed = device_us as i128 - self.device_origin as i128;
+        let scaled = elapsed * self.host_span as i128 / self.device_span as i128;
+
+        (self.host_origin as i128 + scaled).max(0) as u64
+    }
+
+    /// Drift of the device clock relative to the host clock, in parts per
+    /// million
+    ///
+    /// A positive drift indicates that the device clock runs slow relative to
+    /// the host clock.
+    pub fn drift_ppm(&self) -> f32 {
+        let difference = self.host_span as i128 - self.device_span as i128;
+
+        (difference * 1_000_000 / self.device_span as i128) as f32
+    }
+}
+
+/// Correlates device timestamps with a host monotonic clock
+///
+/// The host clock is read using the provided `now` function, which must return
+/// a monotonically increasing time in microseconds. Each device timestamp
+/// passed to [`ClockSync::observe`] should be extended to microseconds, for
+/// example using [`TimestampTracker::micros`](crate::TimestampTracker::micros),
+/// and observed as soon as possible after it has been read so that bus latency
+/// does not skew the mapping.
+///
+/// The drift estimate is taken over the span between the first and the most
+/// recent observations, and so becomes more accurate the longer the devices
+/// are observed.
+#[derive(Clone, Copy, Debug)]
+pub struct ClockSync<F> {
+    /// Host monotonic clock, in microseconds
+    now: F,
+    /// First observed pair of device and host times
+    first: Option<(u64, u64)>,
+    /// Most recently observed pair of device and host times
+    last: Option<(u64, u64)>,
+}
+
+impl<F> ClockSync<F>
+where
+    F: FnMut() -> u64,
+{
+    /// Create a new clock correlator using the provided host clock
+    pub fn new(now: F) -> Self {
+        Self {
+            now,
+            first: None,
+            last: None,
+        }
+    }
+
+    /// Record a device time alongside the current host time, returning the
+    /// host time
+    pub fn observe(&mut self, device_us: u64) -> u64 {
+        let host_us = (self.now)();
+        let pair = (device_us, host_us);
+
+        if self.first.is_none() {
+            self.first = Some(pair);
+        }
+        self.last = Some(pair);
+
+        host_us
+    }
+
+    /// Return the mapping from device time to host time, if any observations
+    /// have been recorded
+    ///
+    /// Until observations spanning some device time have been recorded, the
+    /// mapping assumes that both clocks run at the same rate.
+    pub fn mapping(&self) -> Option<ClockMapping> {
+        let (device_first, host_first) = self.first?;
+        let (device_last, host_last) = self.last?;
+
+        let (device_span, host_span) = if device_last > device_first {
+            (
+                device_last - device_first,
+                host_last.saturating_sub(host_first),
+            )
+        } else {
+            (1, 1)
+        };
+
+        Some(ClockMapping {
+            device_origin: device_last,
+            host_origin: host_last,
+            device_span,
+            host_span,
+        })
+    }
+
+    /// Discard all observations
+    ///
+    /// This must be called whenever the device time is reset, as otherwise the
+    /// discontinuity is interpreted as drift.
+    pub fn reset(&mut self) {
+        self.first = None;
+        self.last = None;
+    }
+
+    /// Return the host clock
+    pub fn release(self) -> F {
+        self.now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a correlator whose host clock returns each of the given times in
+    /// turn
+    fn clock_sync<const N: usize>(host: [u64; N]) -> ClockSync<impl FnMut() -> u64> {
+        let mut host = host.into_iter();
+
+        ClockSync::new(move || host.next().unwrap())
+    }
+
+    #[test]
+    fn no_mapping_without_observations() {
+        assert_eq!(clock_sync([]).mapping(), None);
+    }
+
+    #[test]
+    fn single_observation_assumes_equal_rates() {
+        let mut sync = clock_sync([5_000]);
+        sync.observe(2_000);
+
+        let mapping = sync.mapping().unwrap();
+        assert_eq!(mapping.to_host(2_000), 5_000);
+        assert_eq!(mapping.to_host(3_000), 6_000);
+        assert_eq!(mapping.drift_ppm(), 0.0);
+    }
+
+    #[test]
+    fn mapping_accounts_for_drift() {
+        // The device clock runs 100ppm slow relative to the host clock.
+        let mut sync = clock_sync([1_000, 1_001_100]);
+        assert_eq!(sync.observe(0), 1_000);
+        assert_eq!(sync.observe(1_000_000), 1_001_100);
+
+        let mapping = sync.mapping().unwrap();
+        assert_eq!(mapping.drift_ppm(), 100.0);
+        assert_eq!(mapping.to_host(1_000_000), 1_001_100);
+        assert_eq!(mapping.to_host(2_000_000), 2_001_200);
+        assert_eq!(mapping.to_host(0), 1_000);
+    }
+
+    #[test]
+    fn mapping_clamps_to_host_epoch() {
+        let mut sync = clock_sync([100, 1_100]);
+        sync.observe(10_000);
+        sync.observe(11_000);
+
+        assert_eq!(sync.mapping().unwrap().to_host(0), 0);
+    }
+
+    #[test]
+    fn reset_discards_observations() {
+        let mut sync = clock_sync([100]);
+        sync.observe(10_000);
+        sync.reset();
+
+        assert_eq!(sync.mapping(), None);
+    }
+}
@@ -0,0 +1,299 @@
+//! Temperature compensation of the gyroscope bias
+//!
+//! The bias of the gyroscope drifts as the temperature of the die changes,
+//! which a single bias measured at one temperature cannot account for. A
+//! [`GyroTempModel`] describes the bias as a function of temperature, either
+//! using coefficients characterised ahead of time or a table of biases learned
+//! while the device is stationary at various temperatures.
+
+use core::fmt::Debug;
+
+use micromath::vector::F32x3;
+
+use crate::{error::SensorError, mode::PowerState, Icm42670, Interface};
+
+/// Table of gyroscope biases learned at various temperatures
+///
+/// The bias at any temperature is linearly interpolated between the nearest
+/// learned points, and is clamped to the bias of the coldest or hottest point
+/// outside of the learned range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TempBiasTable {
+    /// Learned points as `(temperature, bias)`, sorted by temperature
+    #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+    points: [(f32, F32x3); TempBiasTable::CAPACITY],
+    /// Number of learned points
+    len: usize,
+}
+
+impl Default for TempBiasTable {
+    fn default() -> Self {
+        Self {
+            points: [(0.0, F32x3::new(0.0, 0.0, 0.0)); Self::CAPACITY],
+            len: 0,
+        }
+    }
+}
+
+impl TempBiasTable {
+    /// Maximum number of points which can be learned
+    pub const CAPACITY: usize = 8;
+
+    /// Points learned within this many degrees centigrade of an existing point
+    /// replace it
+    const RESOLUTION: f32 = 1.0;
+
+    /// Create a new table with no points learned
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Learn the bias measured at the given temperature, in degrees per second
+    /// and degrees centigrade respectively
+    ///
+    /// Any point previously learned within one degree of `temperature` is
+    /// replaced. Returns [`SensorError::TemperatureTableFull`] if the table
+    /// already holds [`TempBiasTable::CAPACITY`] points and none of them are
+    /// replaced, or [`SensorError::InvalidTemperature`] if `temperature` is
+    /// not finite.
+    pub fn learn(&mut self, temperature: f32, bias: F32x3) -> Result<(), SensorError> {
+        if !temperature.is_finite() {
+            return Err(SensorError::InvalidTemperature);
+        }
+
+        let points = &mut self.points[..self.len];
+
+        if let Some(point) = points.iter_mut().find(|(t, _)| {
+            let distance = *t - temperature;
+            -Self::RESOLUTION < distance && distance < Self::RESOLUTION
+        }) {
+            *point = (temperature, bias);
+        } else if self.len < Self::CAPACITY {
+            self.points[self.len] = (temperature, bias);
+            self.len += 1;
+        } else {
+            return Err(SensorError::TemperatureTableFull);
+        }
+
+        // Both appending and replacing a point may leave it out of order, so
+        // always re-sort.
+        // The table is tiny, so an insertion sort is more than adequate.
+        for i in 1..self.len {
+            let mut j = i;
+            while j > 0 && self.points[j - 1].0 > self.points[j].0 {
+                self.points.swap(j - 1, j);
+                j -= 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the learned points as `(temperature, bias)`, sorted by
+    /// temperature
+    pub fn points(&self) -> &[(f32, F32x3)] {
+        &self.points[..self.len]
+    }
+
+    /// Forget all learned points
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Return the bias at the given temperature, or zero if no points have
+    /// been learned
+    ///
+    /// Should the temperature be NaN, the bias of the hottest point is
+    /// returned.
+    pub fn bias(&self, temperature: f32) -> F32x3 {
+        let points = self.points();
+
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return F32x3::new(0.0, 0.0, 0.0),
+        };
+
+        if temperature <= first.0 {
+            return first.1;
+        } else if temperature >= last.0 {
+            return last.1;
+        }
+
+        // Unless the temperature is NaN, it lies strictly within the learned
+        // range, so there is always a pair of points surrounding it.
+        let (lo, hi) = match points
+            .windows(2)
+            .map(|pair| (pair[0], pair[1]))
+            .find(|(_, hi)| temperature <= hi.0)
+        {
+            Some(pair) => pair,
+            None => return last.1,
+        };
+
+        let t = (temperature - lo.0) / (hi.0 - lo.0);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        F32x3::new(
+            lerp(lo.1.x, hi.1.x),
+            lerp(lo.1.y, hi.1.y),
+            lerp(lo.1.z, hi.1.z),
+        )
+    }
+}
+
+/// Model of the gyroscope bias as a function of temperature
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GyroTempModel {
+    /// Quadratic in the deviation from a reference temperature, such that
+    /// `bias = c[0] + c[1] * dt + c[2] * dt²`, where
+    /// `dt = temperature - reference`
+    Polynomial {
+        /// Reference temperature, in degrees centigrade
+        reference: f32,
+        /// Coefficients of each axis, in increasing order
+        #[cfg_attr(feature = "defmt", defmt(Debug2Format))]
+        coefficients: [F32x3; 3],
+    },
+    /// Interpolated from biases learned at various temperatures
+    Table(TempBiasTable),
+}
+
+impl GyroTempModel {
+    /// Return the modelled bias at the given temperature, in degrees per
+    /// second
+    pub fn bias(&self, temperature: f32) -> F32x3 {
+        match self {
+            GyroTempModel::Polynomial {
+                reference,
+                coefficients: [c0, c1, c2],
+            } => {
+                let dt = temperature - reference;
+                let axis = |c0: f32, c1: f32, c2: f32| c0 + (c1 + c2 * dt) * dt;
+
+                F32x3::new(
+                    axis(c0.x, c1.x, c2.x),
+                    axis(c0.y, c1.y, c2.y),
+                    axis(c0.z, c1.z, c2.z),
+                )
+            }
+            GyroTempModel::Table(table) => table.bias(temperature),
+        }
+    }
+
+    /// Subtract the modelled bias at the given temperature from a gyroscope
+    /// measurement, in degrees per second
+    pub(crate) fn compensate(&self, measured: F32x3, temperature: f32) -> F32x3 {
+        let bias = self.bias(temperature);
+
+        F32x3::new(
+            measured.x - bias.x,
+            measured.y - bias.y,
+            measured.z - bias.z,
+        )
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Return the temperature compensation model applied to the gyroscope
+    /// bias, if any
+    pub fn gyro_temp_model(&self) -> Option<GyroTempModel> {
+        self.gyro_temp_model
+    }
+
+    /// Set the temperature compensation model applied to the gyroscope bias
+    ///
+    /// The modelled bias is subtracted from the data returned by
+    /// [`Icm42670::gyro_norm`] and [`Icm42670::read`], before any
+    /// [`Calibration`](crate::Calibration) is applied. As such, the bias of a
+    /// loaded calibration should usually be zero. Note that
+    /// [`Icm42670::gyro_norm`] must additionally read the temperature while a
+    /// model is set.
+    pub fn set_gyro_temp_model(&mut self, model: Option<GyroTempModel>) {
+        self.gyro_temp_model = model;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> TempBiasTable {
+        let mut table = TempBiasTable::new();
+        table.learn(40.0, F32x3::new(2.0, -2.0, 4.0)).unwrap();
+        table.learn(20.0, F32x3::new(0.0, 0.0, 0.0)).unwrap();
+
+        table
+    }
+
+    #[test]
+    fn empty_table_has_no_bias() {
+        assert_eq!(TempBiasTable::new().bias(25.0), F32x3::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn points_are_sorted() {
+        let table = table();
+        let temperatures = table.points().iter().map(|(t, _)| *t);
+
+        assert!(temperatures.eq([20.0, 40.0]));
+    }
+
+    #[test]
+    fn bias_is_interpolated() {
+        let table = table();
+
+        assert_eq!(table.bias(30.0), F32x3::new(1.0, -1.0, 2.0));
+        assert_eq!(table.bias(25.0), F32x3::new(0.5, -0.5, 1.0));
+    }
+
+    #[test]
+    fn bias_is_clamped_outside_learned_range() {
+        let table = table();
+
+        assert_eq!(table.bias(-10.0), F32x3::new(0.0, 0.0, 0.0));
+        assert_eq!(table.bias(85.0), F32x3::new(2.0, -2.0, 4.0));
+    }
+
+    #[test]
+    fn nan_temperature() {
+        let mut table = table();
+
+        assert_eq!(table.bias(f32::NAN), F32x3::new(2.0, -2.0, 4.0));
+        assert!(matches!(
+            table.learn(f32::NAN, F32x3::new(1.0, 1.0, 1.0)),
+            Err(SensorError::InvalidTemperature)
+        ));
+        assert_eq!(table.points().len(), 2);
+    }
+
+    #[test]
+    fn nearby_point_is_replaced() {
+        let mut table = table();
+        table.learn(20.5, F32x3::new(1.0, 1.0, 1.0)).unwrap();
+
+        assert_eq!(table.points().len(), 2);
+        assert_eq!(table.bias(20.5), F32x3::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn full_table_is_rejected() {
+        let mut table = TempBiasTable::new();
+        for i in 0..TempBiasTable::CAPACITY {
+            table
+                .learn(i as f32 * 10.0, F32x3::new(0.0, 0.0, 0.0))
+                .unwrap();
+        }
+
+        assert!(matches!(
+            table.learn(-20.0, F32x3::new(0.0, 0.0, 0.0)),
+            Err(SensorError::TemperatureTableFull)
+        ));
+    }
+}
@@ -0,0 +1,286 @@
+//! Device timestamps
+//!
+//! The device maintains a free-running timestamp counter which may be written
+//! to the FIFO alongside each sample, allowing samples to be tagged with the
+//! time at which they were taken rather than the time at which they were read.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    config::Bitfield,
+    error::SensorError,
+    mode::{MregAccessible, PowerState},
+    register::{field, Bank0, Mreg1, RegisterBank},
+    Error,
+    Icm42670,
+    Interface,
+};
+
+/// Resolution of the timestamp counter
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TimestampResolution {
+    /// 1µs per tick
+    Us1  = 0,
+    /// 16µs per tick
+    Us16 = 1,
+}
+
+impl TimestampResolution {
+    /// Duration of a single tick, in microseconds
+    ///
+    /// When the device is clocked from an external clock on `CLKIN`, a tick
+    /// instead lasts one, or sixteen, periods of that clock.
+    pub fn as_us(self) -> u32 {
+        match self {
+            TimestampResolution::Us1 => 1,
+            TimestampResolution::Us16 => 16,
+        }
+    }
+}
+
+impl Bitfield for TimestampResolution {
+    const BITMASK: u8 = field::TMST_RES.mask();
+
+    fn bits(self) -> u8 {
+        field::TMST_RES.bits(self as u8)
+    }
+}
+
+impl Default for TimestampResolution {
+    fn default() -> Self {
+        Self::Us1
+    }
+}
+
+impl TryFrom<u8> for TimestampResolution {
+    type Error = SensorError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        use TimestampResolution::*;
+
+        match value {
+            0 => Ok(Us1),
+            1 => Ok(Us16),
+            _ => Err(SensorError::InvalidDiscriminant),
+        }
+    }
+}
+
+/// Timestamp configuration
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimestampConfig {
+    /// Enable the timestamp counter
+    pub enabled: bool,
+    /// Resolution of the timestamp counter
+    pub resolution: TimestampResolution,
+    /// Report the time elapsed since the previous sample, rather than the
+    /// absolute value of the counter
+    pub delta: bool,
+    /// Latch the time elapsed between an FSYNC pulse and the following sample,
+    /// readable via [`Icm42670::fsync_timestamp`]
+    pub fsync: bool,
+}
+
+impl TimestampConfig {
+    const BITMASK: u8 = field::TMST_RES.mask()
+        | field::TMST_DELTA_EN.mask()
+        | field::TMST_FSYNC_EN.mask()
+        | field::TMST_EN.mask();
+
+    fn bits(self) -> u8 {
+        self.resolution.bits()
+            | field::TMST_DELTA_EN.bits(self.delta as u8)
+            | field::TMST_FSYNC_EN.bits(self.fsync as u8)
+            | field::TMST_EN.bits(self.enabled as u8)
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        // A single bit is always a valid discriminant, so conversion cannot fail
+        Self {
+            enabled: field::TMST_EN.get(bits) != 0,
+            resolution: TimestampResolution::try_from(field::TMST_RES.get(bits)).unwrap(),
+            delta: field::TMST_DELTA_EN.get(bits) != 0,
+            fsync: field::TMST_FSYNC_EN.get(bits) != 0,
+        }
+    }
+}
+
+impl Default for TimestampConfig {
+    fn default() -> Self {
+        // The timestamp counter is enabled at reset
+        Self {
+            enabled: true,
+            resolution: TimestampResolution::default(),
+            delta: false,
+            fsync: false,
+        }
+    }
+}
+
+/// Host-side accumulator which extends the 16-bit hardware timestamp into a
+/// monotonically increasing 64-bit count of device ticks
+///
+/// The hardware timestamp wraps around every 65,536 ticks, which is roughly
+/// 65ms at a resolution of 1µs and 1s at a resolution of 16µs. A wrap-around
+/// is only detected if successive timestamps passed to
+/// [`TimestampTracker::update`] are less than one wrap-around period apart;
+/// any longer gap is silently under-counted by a multiple of the period.
+///
+/// When every packet read from the FIFO is passed to the tracker, this
+/// requires an output data rate period shorter than 65ms, which is to say
+/// 25Hz or faster, at a resolution of 1µs. At slower output data rates use
+/// [`TimestampResolution::Us16`], which covers every supported rate.
+///
+/// The timestamp counter must be reporting absolute values, which is to say
+/// [`TimestampConfig::delta`] must not be set.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TimestampTracker {
+    /// Resolution of the timestamp counter
+    resolution: TimestampResolution,
+    /// Most recently observed hardware timestamp
+    last: u16,
+    /// Accumulated device ticks
+    ticks: u64,
+}
+
+impl TimestampTracker {
+    /// Create a new timestamp tracker, starting from zero
+    pub fn new(resolution: TimestampResolution) -> Self {
+        Self {
+            resolution,
+            ..Self::default()
+        }
+    }
+
+    /// Update the accumulated ticks using a timestamp read from the device,
+    /// returning the new total
+    pub fn update(&mut self, timestamp: u16) -> u64 {
+        let ticks = timestamp.wrapping_sub(self.last);
+        self.last = timestamp;
+        self.ticks = self.ticks.wrapping_add(ticks as u64);
+
+        self.ticks
+    }
+
+    /// Return the accumulated device ticks
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Return the accumulated device time, in microseconds
+    ///
+    /// See [`TimestampResolution::as_us`] for the duration of a tick when the
+    /// device is clocked from `CLKIN`.
+    pub fn micros(&self) -> u64 {
+        self.ticks * self.resolution.as_us() as u64
+    }
+
+    /// Return the resolution of the timestamp counter
+    pub fn resolution(&self) -> TimestampResolution {
+        self.resolution
+    }
+
+    /// Reset the timestamp tracker, retaining its resolution
+    ///
+    /// This must be called whenever the timestamp counter is reset or
+    /// reconfigured, as otherwise any discontinuity is interpreted as the
+    /// passage of time.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.resolution);
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Read the current timestamp configuration
+    pub fn timestamp_config<D>(&mut self, delay: &mut D) -> Result<TimestampConfig, Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        let bits = self.read_mreg(delay, RegisterBank::MReg1, &Mreg1::TMST_CONFIG1)?;
+
+        Ok(TimestampConfig::from_bits(bits & TimestampConfig::BITMASK))
+    }
+
+    /// Configure the timestamp counter
+    ///
+    /// Timestamps are written to the FIFO only when
+    /// [`FifoContent::timestamp_fsync`](crate::FifoContent::timestamp_fsync) is
+    /// set.
+    pub fn configure_timestamp<D>(
+        &mut self,
+        delay: &mut D,
+        config: TimestampConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+        MODE: MregAccessible,
+    {
+        self.update_mreg(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::TMST_CONFIG1,
+            config.bits(),
+            TimestampConfig::BITMASK,
+        )
+    }
+
+    /// Read the latched FSYNC timestamp
+    ///
+    /// This is the time elapsed between the most recent FSYNC pulse and the
+    /// following sample, in ticks of the configured [`TimestampResolution`].
+    /// It is only latched while [`TimestampConfig::fsync`] is set.
+    pub fn fsync_timestamp(&mut self) -> Result<u16, Error<E>> {
+        let mut buffer = [0u8; 2];
+        self.read_regs(&Bank0::TMST_FSYNCH, &mut buffer)?;
+
+        Ok(self.data_endian.word(buffer) as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracker_accumulates() {
+        let mut tracker = TimestampTracker::new(TimestampResolution::Us1);
+
+        assert_eq!(tracker.update(1_000), 1_000);
+        assert_eq!(tracker.update(6_000), 6_000);
+        assert_eq!(tracker.ticks(), 6_000);
+        assert_eq!(tracker.micros(), 6_000);
+    }
+
+    #[test]
+    fn tracker_handles_wrap() {
+        let mut tracker = TimestampTracker::new(TimestampResolution::Us16);
+
+        tracker.update(65_000);
+        assert_eq!(tracker.update(464), 65_000 + 1_000);
+        assert_eq!(tracker.update(65_000), 65_000 + 1_000 + 64_536);
+        assert_eq!(tracker.update(0), 131_072);
+        assert_eq!(tracker.micros(), 131_072 * 16);
+    }
+
+    #[test]
+    fn tracker_reset_retains_resolution() {
+        let mut tracker = TimestampTracker::new(TimestampResolution::Us16);
+        tracker.update(1_000);
+        tracker.reset();
+
+        assert_eq!(tracker.ticks(), 0);
+        assert_eq!(tracker.resolution(), TimestampResolution::Us16);
+        assert_eq!(tracker.update(500), 500);
+    }
+}
@@ -0,0 +1,51 @@
+//! Tracing of register accesses
+//!
+//! When the `log` or `defmt` feature is enabled, every register read and write
+//! is traced at the trace level, including the address and value of the
+//! register and the bank in which it resides. Accesses to the MREG banks are
+//! traced both as a whole and as the individual accesses to bank 0 which
+//! perform them, so that the trace may be compared directly against a
+//! capture of the bus.
+
+use crate::Bank;
+
+/// Name of the bank in which a register resides
+#[cfg(any(feature = "log", feature = "defmt"))]
+fn bank_name(bank: Bank) -> &'static str {
+    match bank {
+        Bank::Bank0 => "BANK0",
+        Bank::MReg1 => "MREG1",
+        Bank::MReg2 => "MREG2",
+        Bank::MReg3 => "MREG3",
+    }
+}
+
+/// Trace a read of one or more consecutive registers
+#[allow(unused_variables)]
+#[inline(always)]
+pub(crate) fn read(bank: Bank, addr: u8, values: &[u8]) {
+    #[cfg(feature = "log")]
+    log::trace!("read  {} {:#04x}: {:#04x?}", bank_name(bank), addr, values);
+    #[cfg(feature = "defmt")]
+    defmt::trace!(
+        "read  {=str} {=u8:#04x}: {=[u8]:#04x}",
+        bank_name(bank),
+        addr,
+        values
+    );
+}
+
+/// Trace a write of a single register
+#[allow(unused_variables)]
+#[inline(always)]
+pub(crate) fn write(bank: Bank, addr: u8, value: u8) {
+    #[cfg(feature = "log")]
+    log::trace!("write {} {:#04x}: {:#04x}", bank_name(bank), addr, value);
+    #[cfg(feature = "defmt")]
+    defmt::trace!(
+        "write {=str} {=u8:#04x}: {=u8:#04x}",
+        bank_name(bank),
+        addr,
+        value
+    );
+}
@@ -0,0 +1,237 @@
+//! Wake on Motion (WoM) detection
+//!
+//! Wake on Motion compares successive accelerometer samples against per-axis
+//! thresholds, raising an interrupt when the difference exceeds them. This
+//! allows the host to sleep until the device is moved.
+
+use core::fmt::Debug;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    config::{ApexFeatures, Bitfield, InterruptPin, PowerMode},
+    error::SensorError,
+    mode::PowerState,
+    register::{field, Bank0, Mreg1, RegisterBank},
+    Error,
+    Icm42670,
+    Interface,
+};
+
+/// Sample against which the current accelerometer sample is compared
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WomMode {
+    /// Compare against the first sample taken after Wake on Motion was
+    /// enabled, detecting any change in orientation
+    InitialSample  = 0,
+    /// Compare against the previous sample, detecting changes in
+    /// acceleration
+    PreviousSample = 1,
+}
+
+impl Bitfield for WomMode {
+    const BITMASK: u8 = field::WOM_MODE.mask();
+
+    fn bits(self) -> u8 {
+        field::WOM_MODE.bits(self as u8)
+    }
+}
+
+impl Default for WomMode {
+    fn default() -> Self {
+        Self::InitialSample
+    }
+}
+
+/// Combination of axes required to raise a Wake on Motion interrupt
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WomInterruptMode {
+    /// Raise an interrupt when any enabled axis exceeds its threshold
+    Or  = 0,
+    /// Raise an interrupt only when all enabled axes exceed their thresholds
+    And = 1,
+}
+
+impl Bitfield for WomInterruptMode {
+    const BITMASK: u8 = field::WOM_INT_MODE.mask();
+
+    fn bits(self) -> u8 {
+        field::WOM_INT_MODE.bits(self as u8)
+    }
+}
+
+impl Default for WomInterruptMode {
+    fn default() -> Self {
+        Self::Or
+    }
+}
+
+/// Wake on Motion configuration
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WomConfig {
+    /// Threshold for the X axis, with a resolution of 1g/256 (~3.9 mg) per LSB
+    pub x_threshold: u8,
+    /// Threshold for the Y axis, with a resolution of 1g/256 (~3.9 mg) per LSB
+    pub y_threshold: u8,
+    /// Threshold for the Z axis, with a resolution of 1g/256 (~3.9 mg) per LSB
+    pub z_threshold: u8,
+    /// Sample against which the current sample is compared
+    pub mode: WomMode,
+    /// Combination of axes required to raise an interrupt
+    pub interrupt_mode: WomInterruptMode,
+    /// Interrupt pin to route Wake on Motion events to, if any
+    pub interrupt: Option<InterruptPin>,
+}
+
+impl WomConfig {
+    /// Convert a threshold in milli-g to its register value
+    ///
+    /// Thresholds have a resolution of 1g/256 (~3.9 mg) per LSB and are rounded
+    /// to the nearest representable value; the largest threshold which can be
+    /// represented is 996 mg.
+    pub fn mg_to_threshold(mg: u16) -> Result<u8, SensorError> {
+        let threshold = (mg as u32 * 256 + 500) / 1000;
+        u8::try_from(threshold).map_err(|_| SensorError::ThresholdOutOfRange)
+    }
+
+    /// Set the thresholds for all three axes, in milli-g
+    pub fn threshold_mg(self, mg: u16) -> Result<Self, SensorError> {
+        self.axis_thresholds_mg(mg, mg, mg)
+    }
+
+    /// Set the thresholds for each axis individually, in milli-g
+    pub fn axis_thresholds_mg(self, x: u16, y: u16, z: u16) -> Result<Self, SensorError> {
+        Ok(Self {
+            x_threshold: Self::mg_to_threshold(x)?,
+            y_threshold: Self::mg_to_threshold(y)?,
+            z_threshold: Self::mg_to_threshold(z)?,
+            ..self
+        })
+    }
+}
+
+/// Axes on which motion was detected
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WomEvent {
+    /// Motion detected on the X axis
+    pub x: bool,
+    /// Motion detected on the Y axis
+    pub y: bool,
+    /// Motion detected on the Z axis
+    pub z: bool,
+}
+
+impl WomEvent {
+    /// Was motion detected on any axis?
+    pub fn any(&self) -> bool {
+        self.x || self.y || self.z
+    }
+}
+
+impl<DI, E> Icm42670<DI>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+{
+    /// Enable Wake on Motion detection
+    ///
+    /// Once configured, the device is placed into
+    /// [`PowerMode::AccelLowPower`], which disables the gyroscope, so that
+    /// motion can be detected while drawing as little current as possible.
+    pub fn enable_wake_on_motion<D>(
+        &mut self,
+        delay: &mut D,
+        config: WomConfig,
+    ) -> Result<(), Error<E>>
+    where
+        D: DelayUs<u8>,
+    {
+        let features = ApexFeatures {
+            wake_on_motion: true,
+            ..self.apex
+        };
+        features.validate()?;
+
+        // The thresholds are held in MREG1, which cannot be accessed while the
+        // accelerometer is in low-power mode using the wake-up oscillator, so they
+        // must be written before changing power modes.
+        // `ACCEL_WOM_X_THR` through `ACCEL_WOM_Z_THR`
+        let thresholds = [config.x_threshold, config.y_threshold, config.z_threshold];
+        self.write_mregs(
+            delay,
+            RegisterBank::MReg1,
+            &Mreg1::ACCEL_WOM_X_THR,
+            &thresholds,
+        )?;
+
+        for pin in [InterruptPin::Int1, InterruptPin::Int2] {
+            let enable = match pin {
+                InterruptPin::Int1 => field::WOM_INT1_EN,
+                InterruptPin::Int2 => field::WOM_INT2_EN,
+            };
+
+            // Route the interrupts of all three axes, or none of them
+            let bits = if config.interrupt == Some(pin) {
+                enable.max()
+            } else {
+                0
+            };
+            self.write_field(enable, bits)?;
+        }
+
+        // No registers may be written for 200µs after the accelerometer has been
+        // switched on.
+        self.set_power_mode(PowerMode::AccelLowPower)?;
+        delay.delay_us(200);
+
+        let bits = config.interrupt_mode.bits() | config.mode.bits() | field::WOM_EN.bits(1);
+        let mask = WomInterruptMode::BITMASK | WomMode::BITMASK | field::WOM_EN.mask();
+        self.update_reg(&Bank0::WOM_CONFIG, bits, mask)?;
+        self.apex = features;
+
+        Ok(())
+    }
+}
+
+impl<DI, E, MODE> Icm42670<DI, MODE>
+where
+    DI: Interface<Error = E>,
+    E: Debug,
+    MODE: PowerState,
+{
+    /// Disable Wake on Motion detection
+    ///
+    /// The power mode of the device is left unchanged. Significant Motion
+    /// Detection depends on Wake on Motion, so while it is enabled
+    /// [`SensorError::WakeOnMotionRequired`] is returned and Wake on Motion is
+    /// left running.
+    pub fn disable_wake_on_motion(&mut self) -> Result<(), Error<E>> {
+        let features = ApexFeatures {
+            wake_on_motion: false,
+            ..self.apex
+        };
+        features.validate()?;
+
+        self.write_field(field::WOM_EN, 0)?;
+        self.apex = features;
+
+        Ok(())
+    }
+
+    /// Read the axes on which motion has been detected since it
+    /// was last reported
+    pub fn wake_on_motion_status(&mut self) -> Result<WomEvent, Error<E>> {
+        let mask = field::WOM_X_INT.mask() | field::WOM_Y_INT.mask() | field::WOM_Z_INT.mask();
+        let bits = self.take_interrupts(Bank0::INT_STATUS2, mask)?;
+
+        Ok(WomEvent {
+            x: field::WOM_X_INT.get(bits) != 0,
+            y: field::WOM_Y_INT.get(bits) != 0,
+            z: field::WOM_Z_INT.get(bits) != 0,
+        })
+    }
+}